@@ -0,0 +1,402 @@
+//! Step by step simulations of the days that are simulations, for `aoc
+//! animate` to play.
+
+use aoc_core::ParseError;
+use aoc_geometry::Point;
+
+/// A day's simulation, advanced one step at a time.
+pub trait Simulation {
+    /// Takes the next step, returning false, without changing anything, once
+    /// there are none left.
+    fn step(&mut self) -> bool;
+
+    /// One line describing where the simulation is.
+    fn status(&self) -> String;
+
+    /// Draws the current state as lines of text, for an area of `width` by
+    /// `height` cells. Lines that do not fit are clipped.
+    fn draw(&self, width: usize, height: usize) -> Vec<String>;
+
+    /// A copy of the simulation as it is now.
+    fn boxed_clone(&self) -> Box<dyn Simulation>;
+}
+
+/// Starts a day's simulation from its raw puzzle input, following the rules
+/// of the given part.
+pub type Animate = fn(&str, u8) -> Result<Box<dyn Simulation>, ParseError>;
+
+/// Day 05's crane moving crates, one command per step.
+#[derive(Clone)]
+struct Crane {
+    layout: aoc_05::Layout,
+    commands: Vec<aoc_05::Command>,
+    done: usize,
+    /// Whether the crane moves several crates at once, as in part 2.
+    bulk: bool,
+}
+
+pub fn crane(input: &str, part: u8) -> Result<Box<dyn Simulation>, ParseError> {
+    let (layout, commands) = aoc_core::parse::<aoc_05::Day05>(input)?;
+
+    Ok(Box::new(Crane {
+        layout,
+        commands,
+        done: 0,
+        bulk: part == 2,
+    }))
+}
+
+impl Simulation for Crane {
+    fn step(&mut self) -> bool {
+        let Some(cmd) = self.commands.get(self.done) else {
+            return false;
+        };
+
+        if self.bulk {
+            self.layout.cmd_bulk_move(*cmd);
+        } else {
+            self.layout.cmd_move(*cmd);
+        }
+        self.done += 1;
+
+        true
+    }
+
+    fn status(&self) -> String {
+        let crane = if self.bulk { 9001 } else { 9000 };
+        match self.done.checked_sub(1) {
+            Some(last) => format!(
+                "CrateMover {} ran `{}`; {} on top",
+                crane,
+                self.commands[last],
+                self.layout.tops()
+            ),
+            None => format!("CrateMover {}, {} commands", crane, self.commands.len()),
+        }
+    }
+
+    /// The stacks as drawn in the puzzle; only the top crates of tall stacks
+    /// are lost to a short area.
+    fn draw(&self, _: usize, height: usize) -> Vec<String> {
+        let drawing = self.layout.to_string();
+        let lines: Vec<&str> = drawing.lines().collect();
+        let start = lines.len().saturating_sub(height);
+
+        lines[start..].iter().map(|line| line.to_string()).collect()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Day 09's rope, moving its head one position per step.
+#[derive(Clone)]
+struct Rope {
+    map: aoc_09::Map,
+    commands: Vec<aoc_09::Command>,
+    /// Index of the command being run and how many of its steps are done.
+    command: usize,
+    taken: i32,
+}
+
+pub fn rope(input: &str, part: u8) -> Result<Box<dyn Simulation>, ParseError> {
+    let commands = aoc_core::parse::<aoc_09::Day09>(input)?;
+    let map = match part {
+        1 => aoc_09::Map::new(),
+        _ => aoc_09::Config::load().long_rope(),
+    };
+
+    Ok(Box::new(Rope {
+        map,
+        commands,
+        command: 0,
+        taken: 0,
+    }))
+}
+
+impl Simulation for Rope {
+    fn step(&mut self) -> bool {
+        while let Some(cmd) = self.commands.get(self.command) {
+            if self.taken < cmd.steps {
+                self.taken += 1;
+                self.map.move_head(&aoc_09::Command {
+                    direction: cmd.direction,
+                    steps: 1,
+                });
+                return true;
+            }
+            if self.command + 1 == self.commands.len() {
+                break;
+            }
+
+            self.command += 1;
+            self.taken = 0;
+        }
+
+        false
+    }
+
+    fn status(&self) -> String {
+        let visited = self.map.tail().visited().len();
+        match self.commands.get(self.command).filter(|_| self.taken > 0) {
+            Some(cmd) => format!(
+                "`{}`, step {} of {}; the tail has visited {} positions",
+                cmd, self.taken, cmd.steps, visited
+            ),
+            None => format!(
+                "{} knots, {} motions",
+                self.map.knots(),
+                self.commands.len()
+            ),
+        }
+    }
+
+    /// The area around the head, with the knots labelled as in the puzzle
+    /// over the positions the tail visited, `#`, and the start, `s`.
+    fn draw(&self, width: usize, height: usize) -> Vec<String> {
+        let knots = self.map.positions();
+        let corner = knots[0] - Point::new(width as i32 / 2, height as i32 / 2);
+        let label = |i: usize| match (i, knots.len()) {
+            (0, _) => 'H',
+            (_, 2) => 'T',
+            (i, _) => char::from_digit(i as u32, 36).unwrap_or('T'),
+        };
+
+        (0..height as i32)
+            .map(|y| {
+                (0..width as i32)
+                    .map(|x| {
+                        let pos = corner + Point::new(x, y);
+                        match knots.iter().position(|knot| *knot == pos) {
+                            Some(i) => label(i),
+                            None if pos == Point::ORIGIN => 's',
+                            None if self.map.tail().visited().contains(pos) => '#',
+                            None => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Day 10's CPU, running one instruction per step while the CRT's beam
+/// sweeps across the screen.
+#[derive(Clone)]
+struct Crt {
+    vm: aoc_10::VM,
+    commands: Vec<aoc_10::Command>,
+    done: usize,
+}
+
+pub fn crt(input: &str, _: u8) -> Result<Box<dyn Simulation>, ParseError> {
+    Ok(Box::new(Crt {
+        vm: aoc_10::VM::new(&aoc_10::Config::load()),
+        commands: aoc_core::parse::<aoc_10::Day10>(input)?,
+        done: 0,
+    }))
+}
+
+impl Simulation for Crt {
+    fn step(&mut self) -> bool {
+        let Some(cmd) = self.commands.get(self.done) else {
+            return false;
+        };
+
+        self.vm.execute(*cmd);
+        self.done += 1;
+
+        true
+    }
+
+    fn status(&self) -> String {
+        let ran = match self.done.checked_sub(1) {
+            Some(last) => format!("; ran `{}`", self.commands[last]),
+            None => String::new(),
+        };
+
+        format!(
+            "cycle {}, X = {}, signal strength {}{}",
+            self.vm.cycle(),
+            self.vm.register(),
+            self.vm.signal_strength(),
+            ran
+        )
+    }
+
+    /// The screen so far, then the sprite's position on its row and, below
+    /// it, the pixel the beam draws next.
+    fn draw(&self, _: usize, _: usize) -> Vec<String> {
+        let width = self.vm.canvas().width();
+        let beam = (self.vm.cycle() - 1) % width;
+        let sprite: String = (0..width as i32)
+            .map(|x| match (x - self.vm.register()).abs() <= 1 {
+                true => '#',
+                false => '.',
+            })
+            .collect();
+
+        let mut lines: Vec<String> = self.vm.render().lines().map(String::from).collect();
+        lines.push(String::new());
+        lines.push(sprite);
+        lines.push(format!("{:>1$}", '^', beam + 1));
+
+        lines
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Day 11's monkeys, one monkey's turn per step.
+#[derive(Clone)]
+struct Monkeys {
+    sim: aoc_11::Simulator,
+    turns: usize,
+    rounds: usize,
+}
+
+pub fn monkeys(input: &str, part: u8) -> Result<Box<dyn Simulation>, ParseError> {
+    let monkies = aoc_core::parse::<aoc_11::Day11>(input)?;
+    let config = aoc_11::Config::load();
+    let (rounds, relief) = match part {
+        1 => (config.rounds_with_relief, true),
+        _ => (config.rounds_without_relief, false),
+    };
+
+    Ok(Box::new(Monkeys {
+        sim: aoc_11::Simulator::new(monkies, relief),
+        turns: 0,
+        rounds,
+    }))
+}
+
+impl Monkeys {
+    /// The monkey whose turn is next.
+    fn next(&self) -> usize {
+        self.turns % self.sim.monkies().len()
+    }
+}
+
+impl Simulation for Monkeys {
+    fn step(&mut self) -> bool {
+        if self.turns == self.rounds * self.sim.monkies().len() {
+            return false;
+        }
+
+        self.sim.turn(self.next());
+        self.turns += 1;
+
+        true
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "round {} of {}, {} turns taken; monkey business {}",
+            self.turns / self.sim.monkies().len() + 1,
+            self.rounds,
+            self.turns,
+            self.sim.monkey_business()
+        )
+    }
+
+    /// Each monkey's inspection count and the items it holds, with the
+    /// monkey whose turn is next marked.
+    fn draw(&self, _: usize, _: usize) -> Vec<String> {
+        self.sim
+            .monkies()
+            .iter()
+            .enumerate()
+            .map(|(i, monkey)| {
+                let items: Vec<String> = monkey.initial_items.iter().map(i64::to_string).collect();
+                format!(
+                    "{} Monkey {} ({:>6} inspected): {}",
+                    if i == self.next() { '>' } else { ' ' },
+                    i,
+                    self.sim.inspections().get(&i).copied().unwrap_or(0),
+                    items.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(sim: &mut dyn Simulation) -> usize {
+        let mut steps = 0;
+        while sim.step() {
+            steps += 1;
+        }
+
+        steps
+    }
+
+    #[test]
+    fn moves_crates_one_command_at_a_time() {
+        let input = include_str!("../../../days/05/examples/example.txt");
+        let mut sim = crane(&aoc_core::input::normalize(input), 2).unwrap();
+
+        assert!(sim.step());
+        assert_eq!(
+            sim.status(),
+            "CrateMover 9001 ran `move 1 from 2 to 1`; DCP on top"
+        );
+        assert_eq!(run(sim.as_mut()), 3);
+        assert_eq!(sim.draw(20, 2), ["[M] [C] [P]", " 1   2   3 "]);
+    }
+
+    #[test]
+    fn moves_rope_one_position_at_a_time() {
+        let mut sim = rope("R 4\nU 4", 1).unwrap();
+
+        assert_eq!(run(sim.as_mut()), 8);
+        assert_eq!(
+            sim.draw(9, 9)[4..],
+            [
+                "....H....",
+                "....T....",
+                "....#....",
+                "....#....",
+                "s###....."
+            ]
+        );
+    }
+
+    #[test]
+    fn sweeps_crt_beam() {
+        let mut sim = crt("noop\naddx 3\naddx -5", 1).unwrap();
+
+        assert!(sim.step() && sim.step());
+        assert_eq!(
+            sim.status(),
+            "cycle 4, X = 4, signal strength 0; ran `addx 3`"
+        );
+        let lines = sim.draw(40, 10);
+        assert_eq!(&lines[7][..7], "...###.");
+        assert_eq!(lines[8], "   ^");
+    }
+
+    #[test]
+    fn takes_monkey_turns() {
+        let input = include_str!("../../../days/11/examples/example.txt");
+        let mut sim = monkeys(&aoc_core::input::normalize(input), 1).unwrap();
+
+        assert!(sim.step());
+        assert!(sim.draw(80, 10)[1].starts_with(">"));
+        assert_eq!(run(sim.as_mut()), 20 * 4 - 1);
+        assert!(sim.status().ends_with("monkey business 10605"));
+    }
+}
@@ -0,0 +1,380 @@
+//! Running every day at once on a pool of worker threads.
+//!
+//! Each day gets `timeout` from when a worker picks it up. A day that runs
+//! over is reported as timed out and its worker is abandoned, with a fresh
+//! worker taking its place so the remaining days still run. Panics are caught
+//! and reported rather than taking the whole run down.
+//!
+//! The results are summarised in a table with a row per part, checked against
+//! the answers recorded for each day's input, if any.
+
+use std::collections::{HashMap, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_core::{ParseError, Run};
+
+use crate::days::Day;
+use crate::verify::{self, Expected};
+
+/// Name of the worker threads, whose panics are reported in their outcomes
+/// rather than printed.
+const WORKER: &str = "aoc-worker";
+
+/// How a day's run ended.
+#[derive(Debug)]
+pub enum Outcome {
+    Solved(Run),
+    Error(ParseError),
+    Panicked(String),
+    TimedOut,
+    /// The day was not run, e.g. because its input is missing.
+    Skipped(String),
+}
+
+/// A day's outcome and the answers recorded for its input.
+pub struct Entry {
+    pub day: &'static Day,
+    pub outcome: Outcome,
+    pub expected: Option<Expected>,
+}
+
+type Queue = Arc<Mutex<VecDeque<(usize, &'static Day, String)>>>;
+
+enum Message {
+    Started(usize),
+    Finished(usize, Outcome),
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// Starts a worker that runs queued days until the queue is empty.
+fn spawn_worker(queue: Queue, sender: Sender<Message>) {
+    thread::Builder::new()
+        .name(WORKER.to_string())
+        .spawn(move || loop {
+            let Some((i, day, input)) = queue.lock().unwrap().pop_front() else {
+                return;
+            };
+
+            if sender.send(Message::Started(i)).is_err() {
+                return;
+            }
+            let outcome = match panic::catch_unwind(AssertUnwindSafe(|| (day.run)(&input))) {
+                Ok(Ok(run)) => Outcome::Solved(run),
+                Ok(Err(err)) => Outcome::Error(err),
+                Err(payload) => Outcome::Panicked(panic_message(payload)),
+            };
+            if sender.send(Message::Finished(i, outcome)).is_err() {
+                return;
+            }
+        })
+        .expect("cannot start a worker thread");
+}
+
+/// Runs each day on its input using `threads` workers, returning the outcomes
+/// in the order the days were given.
+pub fn run(jobs: Vec<(&'static Day, String)>, threads: usize, timeout: Duration) -> Vec<Outcome> {
+    let count = jobs.len();
+    let queue: Queue = Arc::new(Mutex::new(
+        jobs.into_iter()
+            .enumerate()
+            .map(|(i, (day, input))| (i, day, input))
+            .collect(),
+    ));
+    let (sender, receiver) = mpsc::channel();
+
+    let hook = Arc::new(panic::take_hook());
+    let previous = Arc::clone(&hook);
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(WORKER) {
+            previous(info);
+        }
+    }));
+
+    for _ in 0..threads.clamp(1, count.max(1)) {
+        spawn_worker(Arc::clone(&queue), sender.clone());
+    }
+
+    let mut outcomes: Vec<Option<Outcome>> = (0..count).map(|_| None).collect();
+    let mut running: HashMap<usize, Instant> = HashMap::new();
+    let mut left = count;
+    while left > 0 {
+        let message = match running.values().min() {
+            Some(started) => {
+                let wait = (*started + timeout).saturating_duration_since(Instant::now());
+                receiver.recv_timeout(wait)
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match message {
+            Ok(Message::Started(i)) => {
+                running.insert(i, Instant::now());
+            }
+            Ok(Message::Finished(i, outcome)) => {
+                // Days that already timed out are not reported twice.
+                if running.remove(&i).is_some() {
+                    outcomes[i] = Some(outcome);
+                    left -= 1;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                let expired: Vec<usize> = running
+                    .iter()
+                    .filter(|(_, started)| now.duration_since(**started) >= timeout)
+                    .map(|(i, _)| *i)
+                    .collect();
+
+                for i in expired {
+                    running.remove(&i);
+                    outcomes[i] = Some(Outcome::TimedOut);
+                    left -= 1;
+                    spawn_worker(Arc::clone(&queue), sender.clone());
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("the sender is kept alive"),
+        }
+    }
+
+    // Dropping the quiet hook leaves the only reference to the previous one.
+    drop(panic::take_hook());
+    if let Ok(hook) = Arc::try_unwrap(hook) {
+        panic::set_hook(hook);
+    }
+
+    outcomes
+        .into_iter()
+        .map(|outcome| outcome.expect("every day has finished"))
+        .collect()
+}
+
+/// Status of a part that was solved: whether it matched its recorded answer.
+fn part_status(entry: &Entry, part: u8, mismatches: &[verify::Mismatch]) -> &'static str {
+    let expected = entry.expected.as_ref().and_then(|expected| match part {
+        1 => expected.part1.as_ref(),
+        _ => expected.part2.as_ref(),
+    });
+
+    match expected {
+        None => "ok",
+        Some(_) if mismatches.iter().any(|m| m.part == part) => "FAIL",
+        Some(_) => "pass",
+    }
+}
+
+/// Whether the day was solved with its recorded answers.
+fn passed(entry: &Entry) -> bool {
+    match &entry.outcome {
+        Outcome::Solved(run) => entry
+            .expected
+            .as_ref()
+            .is_none_or(|expected| verify::compare(expected, &run.answers).is_empty()),
+        _ => false,
+    }
+}
+
+/// Whether every day was solved with its recorded answers. Skipped days only
+/// fail the run without `allow_skipped`, and a run that solved nothing fails
+/// regardless.
+pub fn succeeded(entries: &[Entry], allow_skipped: bool) -> bool {
+    entries.iter().any(passed)
+        && entries.iter().all(|entry| match entry.outcome {
+            Outcome::Skipped(_) => allow_skipped,
+            _ => passed(entry),
+        })
+}
+
+/// Prints a table with a row per part of each day, followed by multi-line
+/// answers, the details of every failure and a count of each. Returns whether
+/// the run [`succeeded`].
+pub fn report(entries: &[Entry], allow_skipped: bool) -> bool {
+    let mut rows = vec![];
+    let mut notes = vec![];
+
+    for entry in entries {
+        let day = format!("{:02}", entry.day.number);
+        let run = match &entry.outcome {
+            Outcome::Solved(run) => run,
+            Outcome::Error(err) => {
+                rows.push([day, "-".into(), "-".into(), "-".into(), "ERROR".into()]);
+                notes.push((entry, None, format!("error: {}", err)));
+                continue;
+            }
+            Outcome::Panicked(message) => {
+                rows.push([day, "-".into(), "-".into(), "-".into(), "PANIC".into()]);
+                notes.push((entry, None, format!("panicked: {}", message)));
+                continue;
+            }
+            Outcome::TimedOut => {
+                rows.push([day, "-".into(), "-".into(), "-".into(), "TIMEOUT".into()]);
+                continue;
+            }
+            Outcome::Skipped(reason) => {
+                let status = format!("skipped, {}", reason);
+                rows.push([day, "-".into(), "-".into(), "-".into(), status]);
+                continue;
+            }
+        };
+
+        let mismatches = match &entry.expected {
+            Some(expected) => verify::compare(expected, &run.answers),
+            None => vec![],
+        };
+
+        let parts = [
+            (1, &run.answers.part1, run.timings.part1),
+            (2, &run.answers.part2, run.timings.part2),
+        ];
+        for (part, answer, time) in parts {
+            let shown = if answer.contains('\n') {
+                notes.push((entry, Some(part), answer.clone()));
+                "(below)".to_string()
+            } else {
+                answer.clone()
+            };
+
+            rows.push([
+                day.clone(),
+                part.to_string(),
+                shown,
+                format!("{:.1?}", time),
+                part_status(entry, part, &mismatches).into(),
+            ]);
+        }
+
+        for mismatch in mismatches {
+            notes.push((
+                entry,
+                Some(mismatch.part),
+                format!("expected {}", mismatch.expected),
+            ));
+        }
+    }
+
+    let width = rows
+        .iter()
+        .map(|row| row[2].len())
+        .chain([6])
+        .max()
+        .unwrap();
+    println!("Day  Part  {:<width$}  {:>10}  Status", "Answer", "Time");
+    for [day, part, answer, time, status] in &rows {
+        println!(
+            "{:<3}  {:<4}  {:<width$}  {:>10}  {}",
+            day, part, answer, time, status
+        );
+    }
+
+    for (entry, part, note) in notes {
+        println!();
+        match part {
+            Some(part) => println!("Day {:02} part {}:", entry.day.number, part),
+            None => println!("Day {:02}:", entry.day.number),
+        }
+        for line in note.lines() {
+            println!("    {}", line);
+        }
+    }
+
+    let passed = entries.iter().filter(|entry| passed(entry)).count();
+    let skipped = entries
+        .iter()
+        .filter(|entry| matches!(entry.outcome, Outcome::Skipped(_)))
+        .count();
+    let failed = entries.len() - passed - skipped;
+
+    println!();
+    println!("{} passed, {} failed, {} skipped", passed, failed, skipped);
+
+    succeeded(entries, allow_skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(number: u8, run: fn(&str) -> Result<Run, ParseError>) -> &'static Day {
+        Box::leak(Box::new(Day {
+            number,
+            title: "Test",
+            run,
+            stream: None,
+            draw: None,
+            animate: None,
+            config: None,
+        }))
+    }
+
+    #[test]
+    fn reports_panics_and_timeouts() {
+        let jobs = vec![
+            (day(1, |_| panic!("out of {}", "cheese")), String::new()),
+            (
+                day(2, |_| {
+                    thread::sleep(Duration::from_secs(5));
+                    unreachable!()
+                }),
+                String::new(),
+            ),
+            (crate::days::find(1).unwrap(), "1\n\n2".to_string()),
+            (crate::days::find(9).unwrap(), "X 1".to_string()),
+        ];
+
+        let outcomes = run(jobs, 1, Duration::from_millis(200));
+
+        assert!(matches!(&outcomes[0], Outcome::Panicked(message) if message == "out of cheese"));
+        assert!(matches!(outcomes[1], Outcome::TimedOut));
+        assert!(matches!(&outcomes[2], Outcome::Solved(run) if run.answers.part1 == "2"));
+        assert!(matches!(&outcomes[3], Outcome::Error(err) if err.day == Some(9)));
+    }
+
+    #[test]
+    fn fails_on_unexpected_answers() {
+        let day = crate::days::find(1).unwrap();
+        let entry = |part2| Entry {
+            day,
+            outcome: Outcome::Solved((day.run)("1\n\n2").unwrap()),
+            expected: Some(Expected {
+                path: "01.txt".into(),
+                part1: Some(verify::Answer::Number(2)),
+                part2,
+            }),
+        };
+
+        assert!(succeeded(&[entry(None)], false));
+        assert!(!succeeded(&[entry(Some(verify::Answer::Number(4)))], false));
+    }
+
+    #[test]
+    fn fails_on_skipped_days_unless_allowed() {
+        let day = crate::days::find(1).unwrap();
+        let solved = || Entry {
+            day,
+            outcome: Outcome::Solved((day.run)("1\n\n2").unwrap()),
+            expected: None,
+        };
+        let skipped = || Entry {
+            day,
+            outcome: Outcome::Skipped("no input".into()),
+            expected: None,
+        };
+
+        assert!(!succeeded(&[solved(), skipped()], false));
+        assert!(succeeded(&[solved(), skipped()], true));
+        assert!(!succeeded(&[skipped()], true));
+        assert!(!succeeded(&[], true));
+    }
+}
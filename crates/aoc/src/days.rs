@@ -0,0 +1,192 @@
+use std::io::BufRead;
+
+use aoc_core::config::{Section, Settings};
+use aoc_core::{Answers, ParseError, Run, Solution, StreamError, Streaming};
+
+use crate::animate::{self, Animate};
+use crate::draw::{self, Draw};
+
+/// Solves a puzzle while reading its input.
+pub type Stream = fn(&mut dyn BufRead) -> Result<Answers, StreamError>;
+
+/// Checks a day's section of the workspace configuration.
+pub type CheckConfig = fn(&mut Settings, u8) -> Result<(), String>;
+
+/// A solver registered with the runner.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub run: fn(&str) -> Result<Run, ParseError>,
+    /// Solves while reading the input, for days that support it.
+    pub stream: Option<Stream>,
+    /// Draws a picture of the day's state, for days that have one.
+    pub draw: Option<Draw>,
+    /// Starts the day's simulation, for days that are one.
+    pub animate: Option<Animate>,
+    /// Checks the day's section of the workspace configuration, for days
+    /// with puzzle constants.
+    pub config: Option<CheckConfig>,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            title: S::TITLE,
+            run: aoc_core::run::<S>,
+            stream: None,
+            draw: None,
+            animate: None,
+            config: None,
+        }
+    }
+
+    const fn streaming<S: Streaming>() -> Self {
+        Day {
+            stream: Some(aoc_core::solve_stream::<S>),
+            ..Day::of::<S>()
+        }
+    }
+
+    const fn drawn(self, draw: Draw) -> Self {
+        Day {
+            draw: Some(draw),
+            ..self
+        }
+    }
+
+    const fn animated(self, animate: Animate) -> Self {
+        Day {
+            animate: Some(animate),
+            ..self
+        }
+    }
+
+    const fn configured<C: Section>(self) -> Self {
+        Day {
+            config: Some(Settings::check::<C>),
+            ..self
+        }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::streaming::<aoc_01::Day01>(),
+    Day::of::<aoc_02::Day02>(),
+    Day::streaming::<aoc_03::Day03>().configured::<aoc_03::Config>(),
+    Day::of::<aoc_04::Day04>(),
+    Day::of::<aoc_05::Day05>()
+        .drawn(draw::stacks)
+        .animated(animate::crane),
+    Day::of::<aoc_06::Day06>().configured::<aoc_06::Config>(),
+    Day::of::<aoc_07::Day07>().configured::<aoc_07::Config>(),
+    Day::of::<aoc_08::Day08>().drawn(draw::visible_trees),
+    Day::streaming::<aoc_09::Day09>()
+        .drawn(draw::rope)
+        .animated(animate::rope)
+        .configured::<aoc_09::Config>(),
+    Day::streaming::<aoc_10::Day10>()
+        .drawn(draw::crt)
+        .animated(animate::crt)
+        .configured::<aoc_10::Config>(),
+    Day::of::<aoc_11::Day11>()
+        .animated(animate::monkeys)
+        .configured::<aoc_11::Config>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Checks every section of the workspace configuration belongs to a day
+/// with puzzle constants and holds valid values for it.
+pub fn check_config(settings: &mut Settings) -> Result<(), String> {
+    let configured = || DAYS.iter().filter(|day| day.config.is_some());
+
+    if let Some(name) = settings
+        .sections()
+        .find(|name| !configured().any(|day| aoc_core::config::section_name(day.number) == *name))
+    {
+        let known: Vec<String> = configured()
+            .map(|day| format!("[{}]", aoc_core::config::section_name(day.number)))
+            .collect();
+        return Err(format!(
+            "unknown configuration section [{}], expected one of {}",
+            name,
+            known.join(", ")
+        ));
+    }
+
+    for day in configured() {
+        if let Some(check) = day.config {
+            check(settings, day.number)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_ordered_and_unique() {
+        for pair in DAYS.windows(2) {
+            assert!(pair[0].number < pair[1].number);
+        }
+    }
+
+    #[test]
+    fn streams_line_oriented_days() {
+        let streaming: Vec<u8> = DAYS
+            .iter()
+            .filter(|day| day.stream.is_some())
+            .map(|day| day.number)
+            .collect();
+
+        assert_eq!(streaming, [1, 3, 9, 10]);
+    }
+
+    #[test]
+    fn draws_grid_days() {
+        let drawn: Vec<u8> = DAYS
+            .iter()
+            .filter(|day| day.draw.is_some())
+            .map(|day| day.number)
+            .collect();
+
+        assert_eq!(drawn, [5, 8, 9, 10]);
+    }
+
+    #[test]
+    fn animates_simulation_days() {
+        let animated: Vec<u8> = DAYS
+            .iter()
+            .filter(|day| day.animate.is_some())
+            .map(|day| day.number)
+            .collect();
+
+        assert_eq!(animated, [5, 9, 10, 11]);
+    }
+
+    #[test]
+    fn configures_days_with_puzzle_constants() {
+        let configured: Vec<u8> = DAYS
+            .iter()
+            .filter(|day| day.config.is_some())
+            .map(|day| day.number)
+            .collect();
+
+        assert_eq!(configured, [3, 6, 7, 9, 10, 11]);
+    }
+
+    #[test]
+    fn finds_registered_day() {
+        assert_eq!(
+            find(7).map(|day| day.title),
+            Some("No Space Left On Device")
+        );
+        assert!(find(25).is_none());
+    }
+}
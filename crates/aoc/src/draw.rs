@@ -0,0 +1,77 @@
+//! Pictures of the state behind a day's answers, for the days that have one
+//! worth looking at.
+
+use aoc_core::ParseError;
+use aoc_grid::Grid;
+use aoc_image::Image;
+
+/// Draws a day's picture from its raw puzzle input.
+pub type Draw = fn(&str) -> Result<Image, ParseError>;
+
+/// Day 05's starting stacks, bottom up, with each crate letter in its own
+/// palette index.
+pub fn stacks(input: &str) -> Result<Image, ParseError> {
+    let (layout, _) = aoc_core::parse::<aoc_05::Day05>(input)?;
+    let height = layout.stacks().iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut cells = Grid::new(layout.stacks().len(), height, 0);
+    for (x, stack) in layout.stacks().iter().enumerate() {
+        for (level, c) in stack.iter().enumerate() {
+            cells[(x, height - 1 - level)] = 1 + *c as usize;
+        }
+    }
+
+    Ok(Image::new(cells))
+}
+
+/// Day 08's trees that are visible from outside the forest.
+pub fn visible_trees(input: &str) -> Result<Image, ParseError> {
+    let grid = aoc_core::parse::<aoc_08::Day08>(input)?;
+    Ok(Image::from_grid(&aoc_08::visibility(&grid), |visible| {
+        *visible as usize
+    }))
+}
+
+/// Day 09's positions visited by the tail of the ten knot rope.
+pub fn rope(input: &str) -> Result<Image, ParseError> {
+    let cmds = aoc_core::parse::<aoc_09::Day09>(input)?;
+    let map = aoc_09::simulate(aoc_09::Config::load().long_rope(), &cmds);
+    Ok(Image::from_points(map.tail().visited().iter()))
+}
+
+/// Day 10's CRT after the program has run.
+pub fn crt(input: &str) -> Result<Image, ParseError> {
+    let cmds = aoc_core::parse::<aoc_10::Day10>(input)?;
+    Ok(Image::from_grid(
+        aoc_10::execute(&aoc_10::Config::load(), &cmds).canvas(),
+        |lit| *lit as usize,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pgm(image: Image) -> Vec<u8> {
+        let mut out = vec![];
+        image.write(aoc_image::Format::Pgm, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn draws_stacks_bottom_up() {
+        let image = stacks("    [D]\n[N] [C]\n 1   2 \n\nmove 1 from 2 to 1").unwrap();
+        let pgm = pgm(image);
+
+        // White where there is no crate, black for every crate.
+        assert_eq!(&pgm[pgm.len() - 4..], &[255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn reports_parse_errors_for_the_day() {
+        let err = crt("\nnoop\njump 3").unwrap_err();
+
+        assert_eq!(err.day, Some(10));
+        assert_eq!(err.line, 3);
+    }
+}
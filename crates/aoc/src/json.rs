@@ -0,0 +1,195 @@
+//! Machine-readable output: one JSON object per day, each on its own line.
+//!
+//! Answers are always strings, since some days answer with text or images,
+//! timings are whole nanoseconds, and allocations are counts and bytes
+//! requested. Fields are only ever added, so existing consumers keep working.
+
+use aoc_core::{ParseError, Run, Value};
+use serde::Serialize;
+
+use crate::batch::Outcome;
+use crate::days::Day;
+use crate::verify::{Answer, Expected};
+
+#[derive(Debug, Serialize)]
+pub struct Report<'a> {
+    pub day: u8,
+    pub title: &'a str,
+    #[serde(flatten)]
+    pub status: Status<'a>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Status<'a> {
+    Solved {
+        parse_ns: u128,
+        parse_allocations: u64,
+        parse_allocated_bytes: u64,
+        parts: [Part<'a>; 2],
+        details: &'a Value,
+    },
+    Error {
+        error: Error<'a>,
+    },
+    Panicked {
+        message: String,
+    },
+    TimedOut,
+    Skipped {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Part<'a> {
+    pub part: u8,
+    pub answer: &'a str,
+    /// The recorded answer for the input, if there is one.
+    pub expected: Option<String>,
+    pub solve_ns: u128,
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Error<'a> {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub found: &'a str,
+    pub expected: &'a str,
+}
+
+impl<'a> Status<'a> {
+    fn solved(run: &'a Run, expected: Option<&Expected>) -> Self {
+        let answer = |part: fn(&Expected) -> &Option<Answer>| {
+            expected
+                .and_then(|e| part(e).as_ref())
+                .map(Answer::to_string)
+        };
+        Status::Solved {
+            parse_ns: run.timings.parse.as_nanos(),
+            parse_allocations: run.allocated.parse.count,
+            parse_allocated_bytes: run.allocated.parse.bytes,
+            parts: [
+                Part {
+                    part: 1,
+                    answer: &run.answers.part1,
+                    expected: answer(|e| &e.part1),
+                    solve_ns: run.timings.part1.as_nanos(),
+                    allocations: run.allocated.part1.count,
+                    allocated_bytes: run.allocated.part1.bytes,
+                },
+                Part {
+                    part: 2,
+                    answer: &run.answers.part2,
+                    expected: answer(|e| &e.part2),
+                    solve_ns: run.timings.part2.as_nanos(),
+                    allocations: run.allocated.part2.count,
+                    allocated_bytes: run.allocated.part2.bytes,
+                },
+            ],
+            details: &run.details,
+        }
+    }
+
+    fn error(err: &'a ParseError) -> Self {
+        Status::Error {
+            error: Error {
+                message: err.to_string(),
+                line: err.line,
+                column: err.column,
+                found: &err.found,
+                expected: &err.expected,
+            },
+        }
+    }
+}
+
+impl<'a> Report<'a> {
+    /// Describes the outcome of running a day.
+    pub fn new(day: &'a Day, run: &'a Result<Run, ParseError>) -> Self {
+        let status = match run {
+            Ok(run) => Status::solved(run, None),
+            Err(err) => Status::error(err),
+        };
+
+        Report {
+            day: day.number,
+            title: day.title,
+            status,
+        }
+    }
+
+    /// Describes the outcome of a day run alongside others, along with the
+    /// answers it was expected to give.
+    pub fn batch(day: &'a Day, outcome: &'a Outcome, expected: Option<&Expected>) -> Self {
+        let status = match outcome {
+            Outcome::Solved(run) => Status::solved(run, expected),
+            Outcome::Error(err) => Status::error(err),
+            Outcome::Panicked(message) => Status::Panicked {
+                message: message.clone(),
+            },
+            Outcome::TimedOut => Status::TimedOut,
+            Outcome::Skipped(reason) => Status::Skipped {
+                reason: reason.clone(),
+            },
+        };
+
+        Report {
+            day: day.number,
+            title: day.title,
+            status,
+        }
+    }
+
+    /// Prints the report as a single line.
+    pub fn print(&self) {
+        println!(
+            "{}",
+            serde_json::to_string(self).expect("reports always serialize")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::json;
+
+    #[test]
+    fn reports_solved_day() {
+        let day = crate::days::find(1).unwrap();
+        let run = (day.run)("1000\n\n2000");
+        let report = serde_json::to_value(Report::new(day, &run)).unwrap();
+
+        assert_eq!(report["day"], 1);
+        assert_eq!(report["status"], "solved");
+        assert_eq!(report["parts"][0]["answer"], "2000");
+        assert_eq!(report["parts"][1]["part"], 2);
+        assert!(report["parse_ns"].is_u64());
+        assert!(report["parts"][0]["allocations"].is_u64());
+        assert!(report["parse_allocated_bytes"].is_u64());
+        assert_eq!(report["details"], Value::Null);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let day = crate::days::find(9).unwrap();
+        let run = (day.run)("R 4\nQ 2");
+        let report = serde_json::to_value(Report::new(day, &run)).unwrap();
+
+        assert_eq!(report["status"], "error");
+        assert_eq!(
+            report["error"],
+            json!({
+                "message": "day 09, line 2, column 1: expected `U`, `D`, `L` or `R`, found `Q`",
+                "line": 2,
+                "column": 1,
+                "found": "Q",
+                "expected": "`U`, `D`, `L` or `R`",
+            })
+        );
+    }
+}
@@ -0,0 +1,618 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::prelude::*;
+use std::io::{stdout, BufWriter};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use aoc_client::{Client, Verdict};
+use aoc_core::config::Settings;
+use aoc_core::input::{self, Source};
+use aoc_core::{Answers, Run};
+use aoc_image::{Format as ImageFormat, Palette};
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[global_allocator]
+static ALLOC: aoc_core::alloc::Counting = aoc_core::alloc::Counting;
+
+mod animate;
+mod batch;
+mod days;
+mod draw;
+mod json;
+mod scaffold;
+mod snapshots;
+mod tui;
+mod verify;
+mod watch;
+
+use batch::{Entry, Outcome};
+use days::{Day, DAYS};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Cmd,
+    /// Print the solvers' diagnostics matching this filter to stderr, e.g.
+    /// `aoc_10=trace` or `debug`. Defaults to the `AOC_LOG` variable.
+    #[arg(long, global = true)]
+    log: Option<String>,
+    /// File of puzzle constants, such as day 07's disk size. Defaults to the
+    /// `AOC_CONFIG` variable, or `aoc.toml` if there is one.
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Override a puzzle constant, e.g. `day07.disk_size=50000000`. Can be
+    /// repeated.
+    #[arg(long = "set", global = true, value_name = "DAYNN.KEY=VALUE")]
+    overrides: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// List every registered day.
+    List,
+    /// Run a single day, or every day with `all`.
+    Run {
+        /// Day number, or `all`.
+        day: Selection,
+        /// Puzzle input for a single day. Defaults to the day's file in
+        /// `--inputs`, or stdin if there is none.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Directory holding `NN.txt` inputs.
+        #[arg(long, default_value = input::INPUTS_DIR)]
+        inputs: PathBuf,
+        /// How to print the answers.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Solve a single day while reading its input, keeping memory use
+        /// independent of the input's size. Prints the answers only.
+        #[arg(long)]
+        stream: bool,
+        /// With `all`, how many days to run at once. Defaults to the number
+        /// of CPUs.
+        #[arg(long)]
+        jobs: Option<NonZeroUsize>,
+        /// With `all`, seconds each day may run before it is given up on.
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+        /// With `all`, directory holding the `NN` day crates and the answers
+        /// recorded for their inputs.
+        #[arg(long, default_value = "days")]
+        days: PathBuf,
+        /// With `all`, succeed even if some days were skipped for lack of an
+        /// input, as long as one was solved.
+        #[arg(long)]
+        allow_skipped: bool,
+    },
+    /// Check answers against each day's examples and `expected.toml`.
+    Verify {
+        /// Day number, or `all`.
+        #[arg(default_value = "all")]
+        day: Selection,
+        /// Directory holding the `NN` day crates and their expectations.
+        #[arg(long, default_value = "days")]
+        days: PathBuf,
+    },
+    /// Export a picture of a day's state as a PPM, PGM or SVG image.
+    Draw {
+        /// Day number.
+        day: Selection,
+        /// Image file to write; its extension picks the format.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Puzzle input. Defaults to the day's file in `--inputs`, or stdin if
+        /// there is none.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Directory holding `NN.txt` inputs.
+        #[arg(long, default_value = input::INPUTS_DIR)]
+        inputs: PathBuf,
+        /// Width and height of each cell, in pixels.
+        #[arg(long, default_value = "4")]
+        scale: NonZeroUsize,
+        /// `mono`, `crt`, `rainbow`, or comma separated `#rrggbb` colours
+        /// starting with the background.
+        #[arg(long, default_value = "mono")]
+        palette: Palette,
+    },
+    /// Step through a simulation day in the terminal, playing, pausing and
+    /// rewinding it.
+    Animate {
+        /// Day number.
+        day: Selection,
+        /// Which part's rules to follow.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input. Defaults to the day's file in `--inputs`, or stdin if
+        /// there is none.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Directory holding `NN.txt` inputs.
+        #[arg(long, default_value = input::INPUTS_DIR)]
+        inputs: PathBuf,
+    },
+    /// Solve a day again whenever its input, examples or source change,
+    /// showing how the answers changed.
+    Watch {
+        /// Day number.
+        day: Selection,
+        /// Puzzle input. Defaults to the day's file in `--inputs`.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Directory holding `NN.txt` inputs.
+        #[arg(long, default_value = input::INPUTS_DIR)]
+        inputs: PathBuf,
+        /// Directory holding the `NN` day crates, next to the workspace
+        /// manifest.
+        #[arg(long, default_value = "days")]
+        days: PathBuf,
+    },
+    /// Download a day's puzzle input into `--inputs`, unless it is already
+    /// there. Needs `AOC_SESSION`; `AOC_URL` overrides the site.
+    Fetch {
+        /// Day number.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Directory holding `NN.txt` inputs.
+        #[arg(long, default_value = input::INPUTS_DIR)]
+        inputs: PathBuf,
+    },
+    /// Submit an answer and print the verdict, which is recorded in
+    /// `--inputs`. Answers that were judged before are not sent again.
+    Submit {
+        /// Day number.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part number.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Answer to submit. Defaults to solving the day's input, fetching
+        /// it first if needed.
+        answer: Option<String>,
+        /// Directory holding `NN.txt` inputs.
+        #[arg(long, default_value = input::INPUTS_DIR)]
+        inputs: PathBuf,
+    },
+    /// Create the crate for a new day in `days/NN` from the templates.
+    New {
+        /// Day number.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Puzzle title, as published.
+        #[arg(long)]
+        title: String,
+        /// Directory holding the `NN` day crates, next to the workspace
+        /// manifest.
+        #[arg(long, default_value = "days")]
+        days: PathBuf,
+    },
+    /// Show how the results left by failing snapshot tests differ from the
+    /// committed snapshots, then accept or reject them.
+    Snapshots {
+        /// Replace the committed snapshots with the pending results.
+        #[arg(long, conflicts_with = "reject")]
+        accept: bool,
+        /// Delete the pending results.
+        #[arg(long)]
+        reject: bool,
+        /// Directory searched for pending results.
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Write synthetic puzzle input for a day to stdout.
+    Generate {
+        /// Day number.
+        day: Selection,
+        /// Number of records to generate, e.g. lines, elves or monkeys.
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+        /// Seed for the generator; the same seed gives the same input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Answers for people to read.
+    Text,
+    /// One JSON object per day, with timings, allocations and details.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "all" {
+            return Ok(Selection::All);
+        }
+
+        let number = value
+            .parse::<u8>()
+            .map_err(|_| format!("expected a day number or `all`, got `{}`", value))?;
+
+        match days::find(number) {
+            Some(_) => Ok(Selection::Day(number)),
+            None => Err(format!("day {} is not implemented", number)),
+        }
+    }
+}
+
+fn print_text(day: &Day, answers: Result<&Answers, &dyn Display>) {
+    println!("Day {:02}: {}", day.number, day.title);
+    match answers {
+        Ok(answers) => {
+            for line in answers.to_string().lines() {
+                println!("  {}", line);
+            }
+        }
+        Err(err) => eprintln!("  error: {}", err),
+    }
+}
+
+/// Prints the time each step of the run took and what it allocated.
+fn print_costs(run: &Run) {
+    let steps = [
+        ("parse", run.timings.parse, run.allocated.parse),
+        ("part 1", run.timings.part1, run.allocated.part1),
+        ("part 2", run.timings.part2, run.allocated.part2),
+    ];
+
+    for (step, time, allocated) in steps {
+        println!(
+            "  {:<6} {:>10}  {}",
+            step,
+            format!("{:.1?}", time),
+            allocated
+        );
+    }
+}
+
+/// Runs the day and prints its answers, then what each step cost, or why its
+/// input could not be parsed. Returns whether the day was solved.
+fn print_run(day: &Day, input: &str, format: Format) -> bool {
+    let run = (day.run)(input);
+    match format {
+        Format::Text => {
+            print_text(
+                day,
+                run.as_ref().map(|run| &run.answers).map_err(|err| err as _),
+            );
+            if let Ok(run) = &run {
+                print_costs(run);
+            }
+        }
+        Format::Json => json::Report::new(day, &run).print(),
+    }
+
+    run.is_ok()
+}
+
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    aoc_core::log::init(cli.log.as_deref())?;
+    let mut settings = Settings::load(cli.config.as_deref(), &cli.overrides)?;
+    days::check_config(&mut settings)?;
+    settings.install();
+    let mut solved = true;
+
+    match cli.command {
+        Cmd::List => {
+            for day in DAYS {
+                println!("{:>2}  {}", day.number, day.title);
+            }
+        }
+        Cmd::Run {
+            day: Selection::Day(number),
+            input,
+            inputs,
+            format,
+            stream: true,
+            ..
+        } => {
+            let day = days::find(number).expect("selection is validated on parse");
+            let stream = day
+                .stream
+                .ok_or(format!("day {} cannot be streamed", day.number))?;
+            if format != Format::Text {
+                return Err("--stream only prints text".into());
+            }
+
+            let mut input = Source::find(input.as_deref(), &inputs, day.number).open()?;
+            let answers = stream(&mut input);
+            print_text(day, answers.as_ref().map_err(|err| err as _));
+
+            solved = answers.is_ok();
+        }
+        Cmd::Run {
+            day: Selection::Day(number),
+            input,
+            inputs,
+            format,
+            stream: false,
+            ..
+        } => {
+            let day = days::find(number).expect("selection is validated on parse");
+            let input = Source::find(input.as_deref(), &inputs, day.number).read()?;
+
+            solved = print_run(day, &input, format);
+        }
+        Cmd::Run {
+            day: Selection::All,
+            stream: true,
+            ..
+        } => return Err("--stream needs a single day".into()),
+        Cmd::Run {
+            day: Selection::All,
+            inputs,
+            format,
+            jobs,
+            timeout,
+            days,
+            allow_skipped,
+            ..
+        } => {
+            verify::note_configured(DAYS);
+            let mut inputs: Vec<_> = DAYS
+                .iter()
+                .map(|day| {
+                    let path = input::path(&inputs, day.number);
+                    let input = fs::read_to_string(&path)
+                        .map_err(|err| format!("cannot read {} ({})", path.display(), err));
+                    (day, path, input)
+                })
+                .collect();
+
+            let threads = jobs
+                .or_else(|| thread::available_parallelism().ok())
+                .map_or(1, NonZeroUsize::get);
+            let jobs = inputs
+                .iter_mut()
+                .filter_map(|(day, _, input)| Some((*day, std::mem::take(input.as_mut().ok()?))))
+                .collect();
+            let mut outcomes = batch::run(jobs, threads, Duration::from_secs(timeout)).into_iter();
+
+            let mut entries = vec![];
+            for (day, path, input) in inputs {
+                entries.push(Entry {
+                    day,
+                    outcome: match input {
+                        Ok(_) => outcomes.next().expect("every input was run"),
+                        Err(reason) => Outcome::Skipped(reason),
+                    },
+                    expected: verify::find(&verify::day_dir(&days, day), day.number, &path)?,
+                });
+            }
+
+            match format {
+                Format::Text => solved = batch::report(&entries, allow_skipped),
+                Format::Json => {
+                    for entry in &entries {
+                        json::Report::batch(entry.day, &entry.outcome, entry.expected.as_ref())
+                            .print();
+                    }
+                    solved = batch::succeeded(&entries, allow_skipped);
+                }
+            }
+        }
+        Cmd::Verify { day, days } => {
+            let selected: Vec<&Day> = match day {
+                Selection::All => DAYS.iter().collect(),
+                Selection::Day(number) => days::find(number).into_iter().collect(),
+            };
+
+            verify::note_configured(selected.iter().copied());
+            solved = verify::report(&verify::run(&days, &selected)?);
+        }
+        Cmd::Draw {
+            day,
+            output,
+            input,
+            inputs,
+            scale,
+            palette,
+        } => {
+            let Selection::Day(number) = day else {
+                return Err("draw needs a single day".into());
+            };
+            let day = days::find(number).expect("selection is validated on parse");
+            let draw = day
+                .draw
+                .ok_or(format!("day {} has nothing to draw", day.number))?;
+            if ImageFormat::from_path(&output).is_none() {
+                return Err("--output must end in .ppm, .pgm or .svg".into());
+            }
+            let input = Source::find(input.as_deref(), &inputs, day.number).read()?;
+
+            match draw(&input) {
+                Ok(image) => image
+                    .with_palette(palette)
+                    .with_scale(scale.get())
+                    .save(&output)?,
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    solved = false;
+                }
+            }
+        }
+        Cmd::Animate {
+            day,
+            part,
+            input,
+            inputs,
+        } => {
+            let Selection::Day(number) = day else {
+                return Err("animate needs a single day".into());
+            };
+            let day = days::find(number).expect("selection is validated on parse");
+            let animate = day
+                .animate
+                .ok_or(format!("day {} is not a simulation", day.number))?;
+            let input = Source::find(input.as_deref(), &inputs, day.number).read()?;
+
+            match animate(&input, part) {
+                Ok(sim) => {
+                    let title = format!("Day {:02}: {}, part {}", day.number, day.title, part);
+                    tui::play(&title, sim)?;
+                }
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    solved = false;
+                }
+            }
+        }
+        Cmd::Watch {
+            day,
+            input,
+            inputs,
+            days,
+        } => {
+            let Selection::Day(number) = day else {
+                return Err("watch needs a single day".into());
+            };
+            let day = days::find(number).expect("selection is validated on parse");
+            let input = input.unwrap_or_else(|| input::path(&inputs, day.number));
+
+            verify::note_configured([day]);
+            watch::watch(day, &input, &days)?;
+        }
+        Cmd::Fetch { day, inputs } => {
+            let client = Client::from_env(&inputs)?;
+            let (_, fetched) = client.input(day)?;
+            let path = client.input_path(day);
+
+            if fetched {
+                println!("Fetched {}", path.display());
+            } else {
+                println!("{} is already there", path.display());
+            }
+        }
+        Cmd::Submit {
+            day,
+            part,
+            answer,
+            inputs,
+        } => {
+            let client = Client::from_env(&inputs)?;
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let solver =
+                        days::find(day).ok_or(format!("day {} is not implemented", day))?;
+                    let (input, _) = client.input(day)?;
+                    let answers = (solver.run)(&input)?.answers;
+                    let answer = if part == 1 {
+                        answers.part1
+                    } else {
+                        answers.part2
+                    };
+                    if answer.contains('\n') {
+                        return Err(format!(
+                            "part {} of day {} is a picture; submit what it reads as",
+                            part, day
+                        )
+                        .into());
+                    }
+                    answer
+                }
+            };
+
+            let verdict = match client.previous(day, part, &answer)? {
+                Some(verdict) => {
+                    println!("{} was already submitted: {}", answer, verdict);
+                    verdict
+                }
+                None => {
+                    let verdict = client.submit(day, part, &answer)?;
+                    println!("{}: {}", answer, verdict);
+                    verdict
+                }
+            };
+
+            solved = verdict == Verdict::Correct;
+        }
+        Cmd::New { day, title, days } => {
+            let dir = scaffold::create(&days, day, &title)?;
+            println!("Created {}", dir.display());
+            println!(
+                "Registered Day{:02} in crates/aoc; it runs with `aoc run` once rebuilt.",
+                day
+            );
+        }
+        Cmd::Snapshots {
+            accept,
+            reject,
+            root,
+        } => {
+            let pending = snapshots::pending(&root)?;
+
+            if pending.is_empty() {
+                println!("No pending snapshots");
+            } else if accept {
+                snapshots::accept(&pending)?;
+                println!("Accepted {} snapshots", pending.len());
+            } else if reject {
+                snapshots::reject(&pending)?;
+                println!("Rejected {} snapshots", pending.len());
+            } else {
+                print!("{}", snapshots::review(&pending)?);
+                println!();
+                println!(
+                    "{} pending; keep them with --accept or discard them with --reject",
+                    pending.len()
+                );
+                solved = false;
+            }
+        }
+        Cmd::Generate { day, size, seed } => {
+            let Selection::Day(number) = day else {
+                return Err("generate needs a single day".into());
+            };
+            let generator =
+                aoc_gen::generator(number).ok_or(format!("no generator for day {}", number))?;
+
+            let mut out = BufWriter::new(stdout().lock());
+            aoc_gen::write(generator, size, seed, &mut out)?;
+            out.flush()?;
+        }
+    }
+
+    Ok(if solved {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_selection() {
+        assert_eq!("all".parse(), Ok(Selection::All));
+        assert_eq!("7".parse(), Ok(Selection::Day(7)));
+        assert_eq!("07".parse(), Ok(Selection::Day(7)));
+        assert!("25".parse::<Selection>().is_err());
+        assert!("seven".parse::<Selection>().is_err());
+    }
+
+    #[test]
+    fn verifies_cli() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,259 @@
+//! Creating the crate for a new day from the templates in `templates/`.
+//!
+//! A new day gets a `Solution` stub that parses its input into lines and
+//! answers with placeholders, a `main` reading the input like every other
+//! day, an empty `examples/example.txt` with a sidecar for its answers, and an
+//! `expected.toml` entry for the puzzle input, ready for `aoc verify`. The day
+//! is registered with the runner in `crates/aoc`, so the next build runs it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files of a new day's crate, relative to its directory, and the templates
+/// they are filled in from.
+const TEMPLATES: [(&str, &str); 6] = [
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("examples/example.txt", ""),
+    (
+        "examples/example.toml",
+        include_str!("../templates/example.toml.tmpl"),
+    ),
+    (
+        "expected.toml",
+        include_str!("../templates/expected.toml.tmpl"),
+    ),
+];
+
+/// The runner's crate, relative to the workspace root.
+const RUNNER: &str = "crates/aoc";
+
+/// Fills in a template's `{{day}}`, zero padded `{{NN}}`, `{{title}}` and
+/// quoted `{{title_literal}}` placeholders.
+fn fill(template: &str, day: u8, title: &str) -> String {
+    template
+        .replace("{{day}}", &day.to_string())
+        .replace("{{NN}}", &format!("{:02}", day))
+        .replace("{{title_literal}}", &format!("{:?}", title))
+        .replace("{{title}}", title)
+}
+
+/// Whether the workspace manifest's members include the day's crate in
+/// `days`, e.g. through a `days/*` glob.
+fn is_member(manifest: &str, days: &str, day: u8) -> Result<bool, String> {
+    let manifest: toml::Table =
+        toml::from_str(manifest).map_err(|err| format!("cannot parse manifest ({})", err))?;
+    let members = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(|members| members.as_array())
+        .ok_or("the manifest has no workspace members")?;
+
+    let accepted = [format!("{}/*", days), format!("{}/{:02}", days, day)];
+    Ok(members
+        .iter()
+        .filter_map(|member| member.as_str())
+        .any(|member| accepted.iter().any(|accepted| accepted == member)))
+}
+
+/// Adds the day's crate to the runner's dependencies, keeping them sorted.
+fn add_dependency(manifest: &str, day: u8) -> Result<String, String> {
+    let name = format!("aoc-{:02}", day);
+    let entry = format!("{} = {{ path = \"../../days/{:02}\" }}\n", name, day);
+    let key = |line: &str| line.split('=').next().unwrap_or("").trim().to_string();
+
+    let section = manifest
+        .find("[dependencies]\n")
+        .ok_or("the runner's manifest has no [dependencies]")?;
+    let mut at = section + "[dependencies]\n".len();
+    for line in manifest[at..].split_inclusive('\n') {
+        if line.starts_with('[') || line.trim().is_empty() || key(line) > name {
+            break;
+        }
+        if key(line) == name {
+            return Err(format!("the runner already depends on {}", name));
+        }
+        at += line.len();
+    }
+
+    Ok(format!("{}{}{}", &manifest[..at], entry, &manifest[at..]))
+}
+
+/// Adds the day to the runner's `DAYS`, keeping them in order.
+fn add_day(days: &str, day: u8) -> Result<String, String> {
+    let solver = format!("aoc_{:02}::", day);
+    let entry = format!("    Day::of::<{}Day{:02}>(),\n", solver, day);
+
+    let start = days
+        .find("pub const DAYS: &[Day] = &[\n")
+        .ok_or("the runner has no DAYS")?;
+    let end = start
+        + days[start..]
+            .find("\n];")
+            .ok_or("the runner's DAYS is not closed")?
+        + 1;
+    if days[start..end].contains(&solver) {
+        return Err(format!("the runner already registers day {}", day));
+    }
+
+    // Entries start at the left of the list, and may continue on further,
+    // more indented lines.
+    let number = |entry: &str| {
+        let rest = entry.split("::<aoc_").nth(1)?;
+        rest.get(..2)?.parse::<u8>().ok()
+    };
+    let at = days[start..end]
+        .match_indices("\n    Day::")
+        .map(|(i, _)| start + i + 1)
+        .find(|&i| number(&days[i..end]) > Some(day))
+        .unwrap_or(end);
+
+    Ok(format!("{}{}{}", &days[..at], entry, &days[at..]))
+}
+
+/// Reads one of the runner's files and returns it with the day registered.
+fn register(
+    path: &Path,
+    day: u8,
+    add: fn(&str, u8) -> Result<String, String>,
+) -> Result<(PathBuf, String), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {} ({})", path.display(), err))?;
+    let text =
+        add(&text, day).map_err(|err| format!("cannot update {} ({})", path.display(), err))?;
+
+    Ok((path.to_path_buf(), text))
+}
+
+/// Creates the crate for `day` in `days`, whose parent holds the workspace
+/// manifest, and registers it with the runner. Nothing is written if the
+/// crate already exists, the workspace would not build it, or the runner
+/// cannot take it. Returns the new crate's directory.
+pub fn create(days: &Path, day: u8, title: &str) -> Result<PathBuf, String> {
+    let dir = days.join(format!("{:02}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let root = days.parent().unwrap_or(Path::new(""));
+    let manifest = root.join("Cargo.toml");
+    let text = fs::read_to_string(&manifest)
+        .map_err(|err| format!("cannot read {} ({})", manifest.display(), err))?;
+    let name = days
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or(format!("{} is not a directory name", days.display()))?;
+    if !is_member(&text, name, day)? {
+        return Err(format!(
+            "{} does not include {}/* in its workspace members",
+            manifest.display(),
+            name
+        ));
+    }
+
+    let runner = root.join(RUNNER);
+    let registered = [
+        register(&runner.join("Cargo.toml"), day, add_dependency)?,
+        register(&runner.join("src/days.rs"), day, add_day)?,
+    ];
+
+    for (path, template) in TEMPLATES {
+        let path = dir.join(path);
+        let parent = path.parent().expect("templates are inside the crate");
+        fs::create_dir_all(parent)
+            .and_then(|_| fs::write(&path, fill(template, day, title)))
+            .map_err(|err| format!("cannot write {} ({})", path.display(), err))?;
+    }
+
+    for (path, text) in registered {
+        fs::write(&path, text)
+            .map_err(|err| format!("cannot write {} ({})", path.display(), err))?;
+    }
+
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders() {
+        let lib = fill(TEMPLATES[1].1, 7, r#"The "Big" Day"#);
+
+        assert!(lib.starts_with("//! Day 7: The \"Big\" Day.\n"));
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(lib.contains(r#"const TITLE: &'static str = "The \"Big\" Day";"#));
+        assert!(!lib.contains("{{"));
+    }
+
+    #[test]
+    fn checks_workspace_members() {
+        let manifest = include_str!("../../../Cargo.toml");
+        assert_eq!(is_member(manifest, "days", 12), Ok(true));
+
+        let manifest = "[workspace]\nmembers = [\"crates/*\", \"days/01\"]";
+        assert_eq!(is_member(manifest, "days", 1), Ok(true));
+        assert_eq!(is_member(manifest, "days", 12), Ok(false));
+        assert!(is_member("[package]", "days", 12).is_err());
+    }
+
+    #[test]
+    fn registers_days_in_order() {
+        let manifest = add_dependency(include_str!("../Cargo.toml"), 12).unwrap();
+        assert!(manifest.contains(
+            "aoc-11 = { path = \"../../days/11\" }\naoc-12 = { path = \"../../days/12\" }\naoc-client"
+        ));
+        assert!(add_dependency(&manifest, 12).is_err());
+
+        let days = add_day(include_str!("days.rs"), 12).unwrap();
+        assert!(
+            days.contains(".configured::<aoc_11::Config>(),\n    Day::of::<aoc_12::Day12>(),\n];")
+        );
+        assert!(add_day(&days, 12).is_err());
+
+        let days = "pub const DAYS: &[Day] = &[\n    Day::of::<aoc_01::Day01>(),\n    Day::of::<aoc_03::Day03>()\n        .drawn(draw::x),\n];\n";
+        assert_eq!(
+            add_day(days, 2).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    Day::of::<aoc_01::Day01>(),\n    Day::of::<aoc_02::Day02>(),\n    Day::of::<aoc_03::Day03>()\n        .drawn(draw::x),\n];\n"
+        );
+    }
+
+    #[test]
+    fn creates_day_crate() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let days = root.join("days");
+        fs::create_dir_all(&days).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"days/*\"]",
+        )
+        .unwrap();
+        let runner = root.join(RUNNER);
+        fs::create_dir_all(runner.join("src")).unwrap();
+        fs::write(runner.join("Cargo.toml"), "[dependencies]\n").unwrap();
+        fs::write(
+            runner.join("src/days.rs"),
+            "pub const DAYS: &[Day] = &[\n];\n",
+        )
+        .unwrap();
+
+        let dir = create(&days, 12, "Hill Climbing Algorithm").unwrap();
+        let cargo = fs::read_to_string(dir.join("Cargo.toml"));
+        let example = dir.join("examples/example.txt").is_file();
+        let expected = fs::read_to_string(dir.join("expected.toml"));
+        let registered = fs::read_to_string(runner.join("src/days.rs"));
+        let again = create(&days, 12, "Hill Climbing Algorithm");
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(cargo.unwrap().contains(r#"name = "aoc-12""#));
+        assert!(example);
+        assert!(expected
+            .unwrap()
+            .contains("[[input]]\npath = \"../../inputs/12.txt\""));
+        assert!(registered.unwrap().contains("Day::of::<aoc_12::Day12>(),"));
+        assert!(again.unwrap_err().ends_with("already exists"));
+    }
+}
@@ -0,0 +1,173 @@
+//! Reviewing the results of snapshot tests that no longer match.
+//!
+//! The days check rendered output, like day 10's CRT, against `.snap` files
+//! committed in their `src/snapshots` directories. A test whose output
+//! changed fails and leaves the new output beside the snapshot in a
+//! `.snap.new` file. Those pending results are shown as a diff against the
+//! committed snapshot, then either accepted, replacing it, or rejected.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use similar::{ChangeTag, TextDiff};
+
+/// Extension of a pending result, after the snapshot's own `.snap`.
+const PENDING: &str = ".snap.new";
+
+/// Pending results below `root`, in path order. Build output and hidden
+/// directories are skipped.
+pub fn pending(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut found = vec![];
+    let mut dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries =
+            fs::read_dir(&dir).map_err(|err| format!("cannot read {} ({})", dir.display(), err))?;
+        for entry in entries {
+            let path = entry
+                .map_err(|err| format!("cannot read {} ({})", dir.display(), err))?
+                .path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+            if path.is_dir() {
+                if name != "target" && !name.starts_with('.') {
+                    dirs.push(path);
+                }
+            } else if name.ends_with(PENDING) {
+                found.push(path);
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// The committed snapshot a pending result would replace.
+fn snapshot(pending: &Path) -> PathBuf {
+    pending.with_extension("")
+}
+
+/// A snapshot file's contents, less the header describing where it was
+/// taken.
+fn contents(file: &str) -> &str {
+    let body = file
+        .strip_prefix("---\n")
+        .and_then(|header| header.split_once("\n---\n"))
+        .map_or(file, |(_, body)| body);
+
+    body.trim_end_matches('\n')
+}
+
+/// Line by line differences from `old` to `new`, with removed lines marked
+/// `-` and added ones `+`.
+fn diff(old: &str, new: &str) -> String {
+    let mut out = String::new();
+
+    for change in TextDiff::from_lines(old, new).iter_all_changes() {
+        let sign = match change.tag() {
+            ChangeTag::Equal => ' ',
+            ChangeTag::Delete => '-',
+            ChangeTag::Insert => '+',
+        };
+        let _ = writeln!(
+            out,
+            "    {} {}",
+            sign,
+            change.value().trim_end_matches('\n')
+        );
+    }
+
+    out
+}
+
+/// Describes each pending result and how it differs from the snapshot it
+/// would replace.
+pub fn review(pending: &[PathBuf]) -> Result<String, String> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|err| format!("cannot read {} ({})", path.display(), err))
+    };
+    let mut out = String::new();
+
+    for path in pending {
+        let new = read(path)?;
+        let snapshot = snapshot(path);
+        let (old, status) = match snapshot.exists() {
+            true => (read(&snapshot)?, "changed"),
+            false => (String::new(), "new"),
+        };
+
+        let _ = writeln!(out, "{} ({})", snapshot.display(), status);
+        out.push_str(&diff(contents(&old), contents(&new)));
+    }
+
+    Ok(out)
+}
+
+/// Replaces each snapshot with its pending result.
+pub fn accept(pending: &[PathBuf]) -> Result<(), String> {
+    for path in pending {
+        fs::rename(path, snapshot(path))
+            .map_err(|err| format!("cannot accept {} ({})", path.display(), err))?;
+    }
+
+    Ok(())
+}
+
+/// Deletes the pending results, keeping the snapshots as they are.
+pub fn reject(pending: &[PathBuf]) -> Result<(), String> {
+    for path in pending {
+        fs::remove_file(path)
+            .map_err(|err| format!("cannot reject {} ({})", path.display(), err))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_snapshot_contents() {
+        let old = "---\nsource: days/09/src/lib.rs\n---\n..#\ns##\n";
+        let new = "---\nsource: days/09/src/lib.rs\nassertion_line: 3\n---\n.##\ns##\n";
+
+        assert_eq!(
+            diff(contents(old), contents(new)),
+            "    - ..#\n    + .##\n      s##\n"
+        );
+    }
+
+    #[test]
+    fn accepts_and_rejects_pending_results() {
+        let root = std::env::temp_dir().join(format!("aoc-snapshots-{}", std::process::id()));
+        let dir = root.join("days/10/src/snapshots");
+        fs::create_dir_all(&dir).unwrap();
+        fs::create_dir_all(root.join("target/snapshots")).unwrap();
+        fs::write(root.join("target/snapshots/built.snap.new"), "").unwrap();
+        fs::write(dir.join("crt.snap"), "---\nsource: lib.rs\n---\nold").unwrap();
+        fs::write(dir.join("crt.snap.new"), "---\nsource: lib.rs\n---\nnew").unwrap();
+        fs::write(dir.join("trail.snap.new"), "---\nsource: lib.rs\n---\nnew").unwrap();
+
+        let found = pending(&root).unwrap();
+        assert_eq!(
+            found,
+            [dir.join("crt.snap.new"), dir.join("trail.snap.new")]
+        );
+
+        let review = review(&found).unwrap();
+        assert!(review.contains("crt.snap (changed)\n    - old\n    + new\n"));
+        assert!(review.contains("trail.snap (new)\n    + new\n"));
+
+        accept(&found[..1]).unwrap();
+        reject(&found[1..]).unwrap();
+        let crt = fs::read_to_string(dir.join("crt.snap"));
+        let left = pending(&root.join("days"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(crt.unwrap(), "---\nsource: lib.rs\n---\nnew");
+        assert_eq!(left.unwrap(), Vec::<PathBuf>::new());
+    }
+}
@@ -0,0 +1,328 @@
+//! A terminal player for the simulations in [`crate::animate`], stepping,
+//! playing, pausing and rewinding them.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::animate::Simulation;
+
+/// Most copies of a simulation kept to rewind it; past this, every other
+/// one is dropped.
+const CHECKPOINTS: usize = 32;
+
+/// How long to wait for a key before drawing the next frame.
+const FRAME: Duration = Duration::from_millis(30);
+
+/// Fastest playing speed, in steps per second.
+const MAX_SPEED: u32 = 10_000;
+
+/// The keys, as listed at the foot of the player.
+const HELP: &str =
+    "space play/pause  ←/→ step  PgUp/PgDn 100 steps  Home/End first/last  +/- speed  q quit";
+
+/// A simulation that can be rewound. Copies of it are kept every `interval`
+/// steps; going back replays the steps after the nearest copy before.
+pub struct History {
+    checkpoints: Vec<Box<dyn Simulation>>,
+    interval: usize,
+    current: Box<dyn Simulation>,
+    step: usize,
+    /// Number of steps, once the end has been reached.
+    len: Option<usize>,
+}
+
+impl History {
+    /// The simulation before its first step.
+    pub fn new(sim: Box<dyn Simulation>) -> Self {
+        Self {
+            checkpoints: vec![sim.boxed_clone()],
+            interval: 1,
+            current: sim,
+            step: 0,
+            len: None,
+        }
+    }
+
+    /// The simulation at the current step.
+    pub fn current(&self) -> &dyn Simulation {
+        self.current.as_ref()
+    }
+
+    /// Number of steps taken to get to the current one.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Number of steps in the whole simulation, once it has been played to
+    /// the end.
+    pub fn len(&self) -> Option<usize> {
+        self.len
+    }
+
+    /// Takes the next step, returning whether there was one.
+    pub fn forward(&mut self) -> bool {
+        if self.len == Some(self.step) {
+            return false;
+        }
+        if !self.current.step() {
+            self.len = Some(self.step);
+            return false;
+        }
+
+        self.step += 1;
+        let due = self.step.is_multiple_of(self.interval);
+        if due && self.step / self.interval == self.checkpoints.len() {
+            self.checkpoints.push(self.current.boxed_clone());
+
+            if self.checkpoints.len() > CHECKPOINTS {
+                let mut kept = (0..).map(|i| i % 2 == 0);
+                self.checkpoints.retain(|_| kept.next().unwrap());
+                self.interval *= 2;
+            }
+        }
+
+        true
+    }
+
+    /// Goes to `step`, or to the last one if the simulation ends before it.
+    pub fn seek(&mut self, step: usize) {
+        if step < self.step {
+            let i = (step / self.interval).min(self.checkpoints.len() - 1);
+            self.current = self.checkpoints[i].boxed_clone();
+            self.step = i * self.interval;
+        }
+
+        while self.step < step && self.forward() {}
+    }
+}
+
+/// The player's state between frames.
+struct Player {
+    history: History,
+    playing: bool,
+    /// Steps per second while playing.
+    speed: u32,
+    /// Steps due while playing that have yet to be taken, as whole steps are
+    /// taken at each frame.
+    owed: f64,
+}
+
+impl Player {
+    fn new(sim: Box<dyn Simulation>) -> Self {
+        Self {
+            history: History::new(sim),
+            playing: false,
+            speed: 10,
+            owed: 0.0,
+        }
+    }
+
+    /// Acts on a key press, returning false to quit.
+    fn key(&mut self, code: KeyCode) -> bool {
+        let step = self.history.step();
+        let mut seek = |to: usize| {
+            self.playing = false;
+            self.history.seek(to);
+        };
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Right | KeyCode::Char('l') => seek(step + 1),
+            KeyCode::Left | KeyCode::Char('h') => seek(step.saturating_sub(1)),
+            KeyCode::PageDown => seek(step + 100),
+            KeyCode::PageUp => seek(step.saturating_sub(100)),
+            KeyCode::Home => seek(0),
+            KeyCode::End => seek(usize::MAX),
+            KeyCode::Char(' ') => {
+                self.playing = !self.playing;
+                self.owed = 0.0;
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed = (self.speed * 2).min(MAX_SPEED);
+            }
+            KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Takes the steps due `elapsed` after the last frame while playing,
+    /// pausing at the end.
+    fn advance(&mut self, elapsed: Duration) {
+        if !self.playing {
+            return;
+        }
+
+        self.owed += elapsed.as_secs_f64() * self.speed as f64;
+        while self.owed >= 1.0 {
+            self.owed -= 1.0;
+            if !self.history.forward() {
+                self.playing = false;
+                return;
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, title: &str) {
+        let [body, status, help] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let block = Block::bordered().title(format!(" {} ", title));
+        let area = block.inner(body);
+        let sim = self.history.current();
+        let lines: Vec<Line> = sim
+            .draw(area.width as usize, area.height as usize)
+            .into_iter()
+            .map(Line::from)
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), body);
+
+        let len = self
+            .history
+            .len()
+            .map_or("?".to_string(), |len| len.to_string());
+        let state = match self.playing {
+            true => format!("playing at {} steps/s", self.speed),
+            false => "paused".to_string(),
+        };
+        let line = format!(
+            "step {}/{}, {}: {}",
+            self.history.step(),
+            len,
+            state,
+            sim.status()
+        );
+        frame.render_widget(Paragraph::new(line), status);
+        frame.render_widget(Paragraph::new(HELP).dark_gray(), help);
+    }
+}
+
+/// Plays `sim` in the terminal, starting paused, until `q` is pressed.
+pub fn play(title: &str, sim: Box<dyn Simulation>) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = run(&mut terminal, title, Player::new(sim));
+    ratatui::try_restore()?;
+
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal, title: &str, mut player: Player) -> io::Result<()> {
+    let mut last = Instant::now();
+
+    loop {
+        terminal.draw(|frame| player.draw(frame, title))?;
+
+        if event::poll(FRAME)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !player.key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+
+        let now = Instant::now();
+        player.advance(now - last);
+        last = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end`, one per step.
+    #[derive(Clone)]
+    struct Counter {
+        n: usize,
+        end: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.n == self.end {
+                return false;
+            }
+
+            self.n += 1;
+            true
+        }
+
+        fn status(&self) -> String {
+            self.n.to_string()
+        }
+
+        fn draw(&self, _: usize, _: usize) -> Vec<String> {
+            vec![self.n.to_string()]
+        }
+
+        fn boxed_clone(&self) -> Box<dyn Simulation> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn history(end: usize) -> History {
+        History::new(Box::new(Counter { n: 0, end }))
+    }
+
+    #[test]
+    fn rewinds_from_checkpoints() {
+        let mut history = history(1000);
+
+        history.seek(700);
+        assert_eq!(history.current().status(), "700");
+        assert!(history.checkpoints.len() <= CHECKPOINTS);
+
+        for step in [699, 37, 0, 512] {
+            history.seek(step);
+            assert_eq!(
+                (history.step(), history.current().status()),
+                (step, step.to_string())
+            );
+        }
+        assert_eq!(history.len(), None);
+    }
+
+    #[test]
+    fn stops_at_the_end() {
+        let mut history = history(5);
+
+        history.seek(usize::MAX);
+        assert_eq!((history.step(), history.len()), (5, Some(5)));
+        assert!(!history.forward());
+
+        history.seek(2);
+        assert_eq!(history.current().status(), "2");
+    }
+
+    #[test]
+    fn plays_pauses_and_steps_back() {
+        let mut player = Player::new(Box::new(Counter { n: 0, end: 30 }));
+
+        assert!(player.key(KeyCode::Char(' ')));
+        player.advance(Duration::from_millis(250));
+        assert_eq!(player.history.step(), 2);
+
+        player.key(KeyCode::Left);
+        assert!(!player.playing);
+        assert_eq!(player.history.step(), 1);
+
+        player.key(KeyCode::Char(' '));
+        player.advance(Duration::from_secs(10));
+        assert!(!player.playing);
+        assert_eq!(player.history.len(), Some(30));
+
+        assert!(!player.key(KeyCode::Char('q')));
+    }
+}
@@ -0,0 +1,459 @@
+//! Regression checks against the answers recorded for each day.
+//!
+//! Every `.txt` file in a day's `examples/` directory is an input, with its
+//! answers in a sidecar `.toml` file of the same name:
+//!
+//! ```toml
+//! part1 = 24000
+//! part2 = 45000
+//! ```
+//!
+//! An example without a sidecar, or a part without an answer, is only checked
+//! to solve without errors.
+//!
+//! Inputs kept elsewhere, like puzzle inputs, can be listed in the day's
+//! `expected.toml`:
+//!
+//! ```toml
+//! [[input]]
+//! path = "../../inputs/01.txt"
+//! part1 = 70116
+//! ```
+//!
+//! Puzzle inputs are not committed, so such an entry is reported as missing
+//! rather than failed when the file has not been downloaded.
+//!
+//! Answers are recorded for the puzzle's constants. While the configuration
+//! changes any of a day's, its inputs are only checked to solve without
+//! errors.
+
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use aoc_core::Answers;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::days::Day;
+
+/// Name of the per-day file listing inputs and the answers they must produce.
+pub const EXPECTATIONS: &str = "expected.toml";
+
+/// Name of the per-day directory of example inputs and their answers.
+pub const EXAMPLES: &str = "examples";
+
+#[derive(Debug, Deserialize)]
+struct Expectations {
+    #[serde(default, rename = "input")]
+    inputs: Vec<Expected>,
+}
+
+/// An input and the answers it must produce. Paths are relative to the
+/// expectations file; a part without an answer is not checked.
+#[derive(Debug, Deserialize)]
+pub struct Expected {
+    pub path: PathBuf,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Answers in an example's sidecar file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Sidecar {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// An expected answer, written as a number or a string.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A part whose answer differs from the expected one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Result of checking a day against one of its inputs.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail(Vec<Mismatch>),
+    /// The input could not be read or parsed.
+    Error(String),
+    /// The input file does not exist, e.g. a puzzle input that was not
+    /// downloaded.
+    Missing,
+}
+
+/// The outcome of one expectation.
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub input: PathBuf,
+    pub expected: Expected,
+    pub outcome: Outcome,
+}
+
+/// Directory holding the day's crate, e.g. `days/07`.
+pub fn day_dir(days: &Path, day: &Day) -> PathBuf {
+    days.join(format!("{:02}", day.number))
+}
+
+/// Reads a TOML file, or returns `None` if it does not exist.
+fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("cannot read {} ({})", path.display(), err)),
+    };
+
+    toml::from_str(&text)
+        .map(Some)
+        .map_err(|err| format!("cannot parse {} ({})", path.display(), err))
+}
+
+/// Discovers the inputs in the day's examples directory, in name order.
+fn examples(dir: &Path) -> Result<Vec<Expected>, String> {
+    let examples = dir.join(EXAMPLES);
+    let entries = match fs::read_dir(&examples) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(format!("cannot read {} ({})", examples.display(), err)),
+    };
+
+    let mut inputs = vec![];
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("cannot read {} ({})", examples.display(), err))?
+            .path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+
+    inputs
+        .into_iter()
+        .map(|input| {
+            let answers: Sidecar = read_toml(&input.with_extension("toml"))?.unwrap_or_default();
+            Ok(Expected {
+                path: Path::new(EXAMPLES).join(input.file_name().unwrap()),
+                part1: answers.part1,
+                part2: answers.part2,
+            })
+        })
+        .collect()
+}
+
+/// Reads the expectations of the day in `dir`: its examples, followed by
+/// the inputs listed in its expectations file, if any. Their answers are
+/// dropped if the configuration changes the day's constants.
+pub fn load(dir: &Path, day: u8) -> Result<Vec<Expected>, String> {
+    let mut expectations = examples(dir)?;
+    if let Some(listed) = read_toml::<Expectations>(&dir.join(EXPECTATIONS))? {
+        expectations.extend(listed.inputs);
+    }
+
+    if aoc_core::config::customized(day) {
+        for expected in &mut expectations {
+            expected.part1 = None;
+            expected.part2 = None;
+        }
+    }
+
+    Ok(expectations)
+}
+
+/// Warns on stderr that the answers recorded for the days whose constants
+/// are configured are not checked.
+pub fn note_configured<'a>(days: impl IntoIterator<Item = &'a Day>) {
+    for day in days {
+        if aoc_core::config::customized(day.number) {
+            eprintln!(
+                "note: day {:02} runs with configured constants, so its recorded answers are not checked",
+                day.number
+            );
+        }
+    }
+}
+
+/// The expectation of the day in `dir` for the input at `path`, if it lists
+/// one.
+pub fn find(dir: &Path, day: u8, path: &Path) -> Result<Option<Expected>, String> {
+    let Ok(path) = path.canonicalize() else {
+        return Ok(None);
+    };
+
+    Ok(load(dir, day)?
+        .into_iter()
+        .find(|expected| dir.join(&expected.path).canonicalize().ok() == Some(path.clone())))
+}
+
+/// Answers are compared line by line, ignoring trailing whitespace, so images
+/// such as day 10's survive editors that strip it.
+fn same_answer(expected: &str, actual: &str) -> bool {
+    expected
+        .trim_end()
+        .lines()
+        .map(str::trim_end)
+        .eq(actual.trim_end().lines().map(str::trim_end))
+}
+
+/// Solves the day for an expected input and compares the answers.
+pub fn check(day: &Day, dir: &Path, expected: &Expected) -> Outcome {
+    let path = dir.join(&expected.path);
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Outcome::Missing,
+        Err(err) => return Outcome::Error(format!("cannot read {} ({})", path.display(), err)),
+    };
+
+    let answers = match (day.run)(&input) {
+        Ok(run) => run.answers,
+        Err(err) => return Outcome::Error(err.to_string()),
+    };
+
+    let mismatches = compare(expected, &answers);
+    if mismatches.is_empty() {
+        Outcome::Pass
+    } else {
+        Outcome::Fail(mismatches)
+    }
+}
+
+/// The parts whose answers differ from the expected ones.
+pub fn compare(expected: &Expected, answers: &Answers) -> Vec<Mismatch> {
+    [
+        (1, &expected.part1, &answers.part1),
+        (2, &expected.part2, &answers.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, expected, actual)| {
+        let expected = expected.as_ref()?.to_string();
+        (!same_answer(&expected, actual)).then(|| Mismatch {
+            part,
+            expected,
+            actual: actual.clone(),
+        })
+    })
+    .collect()
+}
+
+/// Checks every expectation registered for the given days.
+pub fn run(days: &Path, selected: &[&Day]) -> Result<Vec<Check>, String> {
+    let mut checks = vec![];
+    for day in selected {
+        let dir = day_dir(days, day);
+        for expected in load(&dir, day.number)? {
+            checks.push(Check {
+                day: day.number,
+                input: expected.path.clone(),
+                outcome: check(day, &dir, &expected),
+                expected,
+            });
+        }
+    }
+
+    Ok(checks)
+}
+
+fn part_status(check: &Check, part: u8) -> &'static str {
+    let expected = match part {
+        1 => &check.expected.part1,
+        _ => &check.expected.part2,
+    };
+
+    match &check.outcome {
+        _ if expected.is_none() => "-",
+        Outcome::Pass => "ok",
+        Outcome::Fail(mismatches) if mismatches.iter().any(|m| m.part == part) => "FAIL",
+        Outcome::Fail(_) => "ok",
+        Outcome::Error(_) | Outcome::Missing => "-",
+    }
+}
+
+pub fn print_value(label: &str, value: &str) {
+    if value.contains('\n') {
+        println!("    {}:", label);
+        for line in value.lines() {
+            println!("      {}", line);
+        }
+    } else {
+        println!("    {:<9} {}", format!("{}:", label), value);
+    }
+}
+
+/// Prints a table with one row per check, followed by the details of every
+/// failure. Returns whether nothing failed; missing inputs are skipped.
+pub fn report(checks: &[Check]) -> bool {
+    let inputs: Vec<String> = checks
+        .iter()
+        .map(|check| check.input.display().to_string())
+        .collect();
+    let width = inputs.iter().map(String::len).chain([5]).max().unwrap();
+
+    println!(
+        "Day  {:<width$}  Part 1  Part 2  Result",
+        "Input",
+        width = width
+    );
+    for (check, input) in checks.iter().zip(&inputs) {
+        let result = match check.outcome {
+            Outcome::Pass => "pass",
+            Outcome::Fail(_) => "FAIL",
+            Outcome::Error(_) => "ERROR",
+            Outcome::Missing => "missing",
+        };
+
+        println!(
+            "{:02}   {:<width$}  {:<6}  {:<6}  {}",
+            check.day,
+            input,
+            part_status(check, 1),
+            part_status(check, 2),
+            result,
+            width = width
+        );
+    }
+
+    for (check, input) in checks.iter().zip(&inputs) {
+        match &check.outcome {
+            Outcome::Fail(mismatches) => {
+                for mismatch in mismatches {
+                    println!();
+                    println!("Day {:02} {}, part {}", check.day, input, mismatch.part);
+                    print_value("expected", &mismatch.expected);
+                    print_value("actual", &mismatch.actual);
+                }
+            }
+            Outcome::Error(err) => {
+                println!();
+                println!("Day {:02} {}", check.day, input);
+                println!("    error: {}", err);
+            }
+            _ => (),
+        }
+    }
+
+    let count = |f: fn(&Outcome) -> bool| checks.iter().filter(|c| f(&c.outcome)).count();
+    let passed = count(|o| *o == Outcome::Pass);
+    let missing = count(|o| *o == Outcome::Missing);
+    let failed = checks.len() - passed - missing;
+
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, DAYS};
+
+    fn days_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../days")
+    }
+
+    #[test]
+    fn every_day_has_examples() {
+        for day in DAYS {
+            let examples = examples(&day_dir(&days_dir(), day)).unwrap();
+            assert!(!examples.is_empty(), "day {} has no examples", day.number);
+            assert!(
+                examples
+                    .iter()
+                    .all(|e| e.part1.is_some() && e.part2.is_some()),
+                "day {} has examples without answers",
+                day.number
+            );
+        }
+    }
+
+    #[test]
+    fn discovers_examples_in_order() {
+        let paths: Vec<PathBuf> = load(&days_dir().join("09"), 9)
+            .unwrap()
+            .into_iter()
+            .map(|expected| expected.path)
+            .collect();
+
+        assert_eq!(
+            paths,
+            [
+                Path::new("examples/example.txt"),
+                Path::new("examples/larger.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn answers_match_expectations() {
+        let checks = run(&days_dir(), &DAYS.iter().collect::<Vec<_>>()).unwrap();
+
+        for check in checks {
+            assert!(
+                matches!(check.outcome, Outcome::Pass | Outcome::Missing),
+                "day {:02} {}: {:?}",
+                check.day,
+                check.input.display(),
+                check.outcome
+            );
+        }
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let expected = Expected {
+            path: "examples/example.txt".into(),
+            part1: Some(Answer::Number(24000)),
+            part2: Some(Answer::Number(1)),
+        };
+        let day = days::find(1).unwrap();
+
+        assert_eq!(
+            check(day, &day_dir(&days_dir(), day), &expected),
+            Outcome::Fail(vec![Mismatch {
+                part: 2,
+                expected: "1".into(),
+                actual: "45000".into(),
+            }])
+        );
+    }
+
+    #[test]
+    fn finds_expectations_by_path() {
+        let dir = days_dir().join("06");
+
+        let found = find(&dir, 6, &dir.join("examples/../examples/example-2.txt")).unwrap();
+        assert_eq!(found.unwrap().part1, Some(Answer::Number(5)));
+        assert!(find(&dir, 6, Path::new("no/such/input.txt"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn ignores_trailing_whitespace() {
+        assert!(same_answer("ab\ncd  \n", "ab  \ncd"));
+        assert!(!same_answer("ab\ncd", "ab\nce"));
+    }
+}
@@ -0,0 +1,425 @@
+//! Re-solving a day whenever its input, examples or source change.
+//!
+//! The day's crate directory and the directory holding its puzzle input are
+//! watched with the operating system's filesystem notifications; nothing is
+//! polled. When an input or example changes, the day is solved again on every
+//! input and the answers are shown next to those of the previous run. When the
+//! day's source changes, `aoc` is rebuilt and restarted in place, carrying the
+//! previous answers over so the diff still covers the change.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use aoc_core::Answers;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::days::Day;
+use crate::verify::{self, Expected, Mismatch};
+
+/// Variable holding the previous run's answers across a restart.
+const PREVIOUS: &str = "AOC_WATCH_PREVIOUS";
+
+/// How long to wait for more changes after one arrives, so that an editor
+/// saving several files, or a file in several writes, triggers a single run.
+const SETTLE: Duration = Duration::from_millis(100);
+
+/// Answers of the previous run, by input label.
+type Previous = BTreeMap<String, [String; 2]>;
+
+/// An input the day is solved on.
+struct Target {
+    /// The path as shown, relative to the day's crate for its examples.
+    label: String,
+    path: PathBuf,
+    expected: Option<Expected>,
+}
+
+/// What solving the day on one target gave.
+struct Solved {
+    label: String,
+    answers: Result<Answers, String>,
+    mismatches: Vec<Mismatch>,
+    expected: [bool; 2],
+}
+
+/// What kind of file changed.
+#[derive(Debug, PartialEq, Eq)]
+enum Change {
+    /// An input, example or expectations file; the day is solved again.
+    Data(PathBuf),
+    /// The day's source or manifest; `aoc` is rebuilt first.
+    Source(PathBuf),
+}
+
+/// The day's examples and listed inputs, followed by its puzzle input unless
+/// it is already listed.
+fn targets(dir: &Path, day: &Day, input: &Path) -> Result<Vec<Target>, String> {
+    let mut targets: Vec<Target> = verify::load(dir, day.number)?
+        .into_iter()
+        .map(|expected| Target {
+            label: expected.path.display().to_string(),
+            path: dir.join(&expected.path),
+            expected: Some(expected),
+        })
+        .collect();
+
+    let canonical = input.canonicalize().ok();
+    let listed = targets
+        .iter()
+        .any(|target| canonical.is_some() && target.path.canonicalize().ok() == canonical);
+    if !listed {
+        targets.push(Target {
+            label: input.display().to_string(),
+            path: input.to_path_buf(),
+            expected: None,
+        });
+    }
+
+    Ok(targets)
+}
+
+/// Solves the day on every target whose file exists.
+fn solve(day: &Day, targets: Vec<Target>) -> Vec<Solved> {
+    targets
+        .into_iter()
+        .filter(|target| target.path.is_file())
+        .map(|target| {
+            let answers = fs::read_to_string(&target.path)
+                .map_err(|err| format!("cannot read {} ({})", target.path.display(), err))
+                .and_then(|input| {
+                    (day.run)(&input)
+                        .map(|run| run.answers)
+                        .map_err(|err| err.to_string())
+                });
+            let mismatches = match (&target.expected, &answers) {
+                (Some(expected), Ok(answers)) => verify::compare(expected, answers),
+                _ => vec![],
+            };
+            let expected = target
+                .expected
+                .as_ref()
+                .map_or([false; 2], |e| [e.part1.is_some(), e.part2.is_some()]);
+
+            Solved {
+                label: target.label,
+                answers,
+                mismatches,
+                expected,
+            }
+        })
+        .collect()
+}
+
+/// The answers to remember for the next run's diff.
+fn remember(solved: &[Solved]) -> Previous {
+    solved
+        .iter()
+        .filter_map(|solved| {
+            let answers = solved.answers.as_ref().ok()?;
+            Some((
+                solved.label.clone(),
+                [answers.part1.clone(), answers.part2.clone()],
+            ))
+        })
+        .collect()
+}
+
+/// Formats each target's answers, marking those that changed since the
+/// `previous` run and those that differ from the recorded answers.
+fn render(solved: &[Solved], previous: &Previous) -> String {
+    let mut out = String::new();
+
+    for solved in solved {
+        let _ = writeln!(out, "{}", solved.label);
+        let answers = match &solved.answers {
+            Ok(answers) => answers,
+            Err(err) => {
+                let _ = writeln!(out, "  error: {}", err);
+                continue;
+            }
+        };
+
+        let before = previous.get(&solved.label);
+        for (i, answer) in [&answers.part1, &answers.part2].into_iter().enumerate() {
+            let part = i as u8 + 1;
+            let was = before.map(|before| &before[i]).filter(|was| *was != answer);
+            let status = match solved.mismatches.iter().find(|m| m.part == part) {
+                Some(m) if m.expected.contains('\n') => "  FAIL".to_string(),
+                Some(m) => format!("  FAIL, expected {}", m.expected),
+                None if solved.expected[i] => "  pass".to_string(),
+                None => String::new(),
+            };
+
+            if !answer.contains('\n') && !was.is_some_and(|was| was.contains('\n')) {
+                let change = was.map_or(String::new(), |was| format!(" (was {})", was));
+                let _ = writeln!(out, "  Part {}: {}{}{}", part, answer, change, status);
+                continue;
+            }
+
+            let change = if was.is_some() { " (changed)" } else { "" };
+            let _ = writeln!(out, "  Part {}:{}{}", part, change, status);
+            let new: Vec<&str> = answer.lines().collect();
+            let old: Vec<&str> = was.map_or(new.clone(), |was| was.lines().collect());
+            for line in 0..new.len().max(old.len()) {
+                match (old.get(line), new.get(line)) {
+                    (Some(old), Some(new)) if old == new => {
+                        let _ = writeln!(out, "      {}", new);
+                    }
+                    (old, new) => {
+                        if let Some(old) = old {
+                            let _ = writeln!(out, "    - {}", old);
+                        }
+                        if let Some(new) = new {
+                            let _ = writeln!(out, "    + {}", new);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// What a changed file means for the watch, if anything. Only Rust sources
+/// and the manifest count as source, and only `.txt` and `.toml` files in the
+/// crate as data, so editors' swap and backup files are ignored.
+fn classify(path: &Path, dir: &Path, input: &Path) -> Option<Change> {
+    if path == input {
+        return Some(Change::Data(path.to_path_buf()));
+    }
+
+    let relative = path.strip_prefix(dir).ok()?;
+    let extension = path.extension().and_then(OsStr::to_str);
+    if relative == Path::new("Cargo.toml")
+        || (relative.starts_with("src") && extension == Some("rs"))
+    {
+        Some(Change::Source(path.to_path_buf()))
+    } else if matches!(extension, Some("txt" | "toml")) {
+        Some(Change::Data(path.to_path_buf()))
+    } else {
+        None
+    }
+}
+
+/// Waits for a relevant change, then for the burst it is part of to settle.
+/// A source change in the burst takes precedence over data changes.
+fn next_change(
+    events: &Receiver<notify::Result<notify::Event>>,
+    dir: &Path,
+    input: &Path,
+) -> Result<Change, String> {
+    let mut change: Option<Change> = None;
+
+    loop {
+        let event = match change {
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(_) => events.recv_timeout(SETTLE),
+        };
+        let event = match event {
+            Ok(event) => event.map_err(|err| format!("cannot watch for changes ({})", err))?,
+            Err(RecvTimeoutError::Timeout) => return Ok(change.expect("waiting to settle")),
+            Err(RecvTimeoutError::Disconnected) => return Err("the watcher stopped".into()),
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            continue;
+        }
+
+        for path in &event.paths {
+            match classify(path, dir, input) {
+                Some(found @ Change::Source(_)) => change = Some(found),
+                Some(found) if change.is_none() => change = Some(found),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Builds `aoc` in the workspace at `root`, with the same profile as this
+/// binary. Cargo's output is shown as is.
+fn rebuild(root: &Path) -> Result<bool, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .args(["build", "--quiet", "--package", "aoc"])
+        .current_dir(root);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command
+        .status()
+        .map(|status| status.success())
+        .map_err(|err| format!("cannot run cargo ({})", err))
+}
+
+/// Replaces this process with a fresh run of `exe` with the same arguments,
+/// handing it the `previous` answers. Only returns if that fails.
+fn restart(exe: &Path, previous: &Previous) -> io::Error {
+    let mut command = Command::new(exe);
+    command.args(env::args_os().skip(1)).env(
+        PREVIOUS,
+        serde_json::to_string(previous).unwrap_or_default(),
+    );
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.exec()
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(err) => err,
+    }
+}
+
+/// Watches the day's crate in `days` and its puzzle `input`, re-solving the
+/// day on every change until interrupted.
+pub fn watch(day: &Day, input: &Path, days: &Path) -> Result<(), String> {
+    // Taken before any rebuild, which replaces the binary.
+    let exe = env::current_exe().map_err(|err| format!("cannot find aoc ({})", err))?;
+    let root = match days.parent() {
+        Some(root) if root != Path::new("") => root,
+        _ => Path::new("."),
+    };
+    let dir = verify::day_dir(days, day);
+    let watched = dir
+        .canonicalize()
+        .map_err(|err| format!("cannot watch {} ({})", dir.display(), err))?;
+
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)
+        .map_err(|err| format!("cannot watch for changes ({})", err))?;
+    watcher
+        .watch(&watched, RecursiveMode::Recursive)
+        .map_err(|err| format!("cannot watch {} ({})", dir.display(), err))?;
+
+    // Editors often replace a file rather than write to it, so the input's
+    // directory is watched rather than the file itself.
+    let parent = match input.parent() {
+        Some(parent) if parent != Path::new("") => parent,
+        _ => Path::new("."),
+    };
+    let watched_input = match (parent.canonicalize(), input.file_name()) {
+        (Ok(parent), Some(name)) => {
+            watcher
+                .watch(&parent, RecursiveMode::NonRecursive)
+                .map_err(|err| format!("cannot watch {} ({})", parent.display(), err))?;
+            parent.join(name)
+        }
+        _ => {
+            eprintln!("{} does not exist; not watching it", parent.display());
+            input.to_path_buf()
+        }
+    };
+
+    let mut previous: Previous = env::var(PREVIOUS)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    println!(
+        "Watching {} and {} for day {:02}: {}",
+        dir.display(),
+        input.display(),
+        day.number,
+        day.title
+    );
+
+    let mut stale = true;
+    loop {
+        if stale {
+            let solved = solve(day, targets(&dir, day, input)?);
+            println!();
+            print!("{}", render(&solved, &previous));
+            previous = remember(&solved);
+        }
+
+        match next_change(&events, &watched, &watched_input)? {
+            Change::Data(path) => {
+                println!("\n{} changed", path.display());
+                stale = true;
+            }
+            Change::Source(path) => {
+                println!("\n{} changed, rebuilding", path.display());
+                if rebuild(root)? {
+                    let err = restart(&exe, &previous);
+                    return Err(format!("cannot restart {} ({})", exe.display(), err));
+                }
+                println!("Build failed; waiting for further changes");
+                stale = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(label: &str, part1: &str, part2: &str, mismatches: Vec<Mismatch>) -> Solved {
+        Solved {
+            label: label.into(),
+            answers: Ok(Answers {
+                part1: part1.into(),
+                part2: part2.into(),
+            }),
+            mismatches,
+            expected: [true, false],
+        }
+    }
+
+    #[test]
+    fn renders_changes_since_previous_run() {
+        let first = [solved("example.txt", "13", "#.\n.#", vec![])];
+        assert_eq!(
+            render(&first, &Previous::new()),
+            "example.txt\n  Part 1: 13  pass\n  Part 2:\n      #.\n      .#\n"
+        );
+
+        let mismatch = Mismatch {
+            part: 1,
+            expected: "13".into(),
+            actual: "14".into(),
+        };
+        let second = [solved("example.txt", "14", "#.\n##", vec![mismatch])];
+        assert_eq!(
+            render(&second, &remember(&first)),
+            "example.txt\n  Part 1: 14 (was 13)  FAIL, expected 13\n  Part 2: (changed)\n      #.\n    - .#\n    + ##\n"
+        );
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let dir = Path::new("/aoc/days/09");
+        let input = Path::new("/aoc/inputs/09.txt");
+        let change = |path: &str| classify(Path::new(path), dir, input);
+
+        assert_eq!(
+            change("/aoc/inputs/09.txt"),
+            Some(Change::Data(input.into()))
+        );
+        assert!(matches!(
+            change("/aoc/days/09/src/lib.rs"),
+            Some(Change::Source(_))
+        ));
+        assert!(matches!(
+            change("/aoc/days/09/Cargo.toml"),
+            Some(Change::Source(_))
+        ));
+        assert!(matches!(
+            change("/aoc/days/09/examples/a.toml"),
+            Some(Change::Data(_))
+        ));
+        assert_eq!(change("/aoc/days/09/src/.lib.rs.swp"), None);
+        assert_eq!(change("/aoc/inputs/10.txt"), None);
+    }
+}
@@ -0,0 +1,41 @@
+use aoc_core::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+fn bench<S: Solution>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+
+    for size in aoc_bench::sizes(S::DAY) {
+        let input = aoc_bench::input(S::DAY, size);
+        let parsed = S::parse(&input).unwrap();
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("parse", size), &input, |b, input| {
+            b.iter(|| S::parse(input))
+        });
+        group.bench_with_input(BenchmarkId::new("part1", size), &parsed, |b, parsed| {
+            b.iter(|| S::part1(parsed))
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &parsed, |b, parsed| {
+            b.iter(|| S::part2(parsed))
+        });
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench::<aoc_01::Day01>(c);
+    bench::<aoc_02::Day02>(c);
+    bench::<aoc_03::Day03>(c);
+    bench::<aoc_04::Day04>(c);
+    bench::<aoc_05::Day05>(c);
+    bench::<aoc_06::Day06>(c);
+    bench::<aoc_07::Day07>(c);
+    bench::<aoc_08::Day08>(c);
+    bench::<aoc_09::Day09>(c);
+    bench::<aoc_10::Day10>(c);
+    bench::<aoc_11::Day11>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
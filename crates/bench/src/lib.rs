@@ -0,0 +1,29 @@
+//! Inputs for the benchmarks, generated with a fixed seed so that runs can be
+//! compared with each other.
+//!
+//! ```
+//! let input = aoc_bench::input(2, 3);
+//! assert_eq!(input.lines().count(), 3);
+//! ```
+
+/// Seed every benchmark input is generated with.
+pub const SEED: u64 = 2022;
+
+/// Input sizes benchmarked for a day, as understood by its generator.
+pub fn sizes(day: u8) -> [usize; 3] {
+    match day {
+        // Side of the square forest.
+        8 => [10, 50, 100],
+        // Monkeys, each playing 10,000 rounds in part 2.
+        11 => [4, 16, 64],
+        _ => [100, 1_000, 10_000],
+    }
+}
+
+/// The day's benchmark input of the given size, normalised as the runner
+/// would before parsing it.
+pub fn input(day: u8, size: usize) -> String {
+    let input = aoc_gen::generate(day, size, SEED)
+        .unwrap_or_else(|| panic!("no generator for day {}", day));
+    aoc_core::input::normalize(&input)
+}
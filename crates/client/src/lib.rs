@@ -0,0 +1,511 @@
+//! Fetching puzzle inputs and submitting answers over HTTP.
+//!
+//! Requests are made with the session token from the `AOC_SESSION` variable,
+//! to the site at `AOC_URL` if set, so everything can be pointed at a local
+//! server instead. Inputs are cached as `NN.txt` files in the inputs directory
+//! and only fetched when missing. Submissions are recorded in the same
+//! directory along with the server's [`Verdict`], so an answer is never sent
+//! twice.
+//!
+//! ```
+//! use aoc_client::Verdict;
+//!
+//! let html = "<main><article><p>That's not the right answer; \
+//!             your answer is too high.</p></article></main>";
+//! assert_eq!(Verdict::parse(html), Verdict::TooHigh);
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Where puzzles are fetched from unless `AOC_URL` says otherwise.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Variable holding the session cookie of a logged in browser.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Variable overriding [`BASE_URL`], e.g. to test against a local server.
+pub const URL_ENV: &str = "AOC_URL";
+
+/// The event this repository solves.
+pub const YEAR: u16 = 2022;
+
+/// File in the inputs directory recording every submission.
+const SUBMISSIONS: &str = "submissions.jsonl";
+
+const USER_AGENT: &str = concat!("aoc-client/", env!("CARGO_PKG_VERSION"));
+
+/// The server's judgement of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way.
+    Incorrect,
+    /// Another answer was submitted too recently; `wait` is how long is
+    /// left, as the server puts it, e.g. `"1m 2s"`.
+    TooSoon {
+        wait: Option<String>,
+    },
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// A response that none of the above matched, as text.
+    Unrecognised(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the text of the page returned for a submission.
+    pub fn parse(html: &str) -> Self {
+        let text = article(html);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Verdict::TooSoon { wait }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unrecognised(text)
+        }
+    }
+
+    /// Whether the verdict settles the answer, so submitting it again would
+    /// get the same one.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooSoon { wait: Some(wait) } => write!(f, "too soon, wait {}", wait),
+            Verdict::TooSoon { wait: None } => write!(f, "too soon"),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked"),
+            Verdict::Unrecognised(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, or of the whole page if it has none,
+/// without tags and with runs of whitespace collapsed.
+fn article(html: &str) -> String {
+    let body = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A submission and its verdict, as recorded in the inputs directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Failure to fetch an input or submit an answer.
+#[derive(Debug)]
+pub enum Error {
+    /// `AOC_SESSION` is not set.
+    NoSession,
+    /// The server refused the request, e.g. with 404 for a puzzle that is
+    /// not unlocked yet or 400 for an expired session.
+    Status { url: String, status: u16 },
+    /// The server could not be reached.
+    Transport(String),
+    /// The inputs directory could not be read or written.
+    Io(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => Error::Status {
+                url: response.get_url().to_string(),
+                status,
+            },
+            ureq::Error::Transport(err) => Error::Transport(err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSession => write!(f, "set {} to your session cookie", SESSION_ENV),
+            Error::Status { url, status } => write!(f, "{} answered {}", url, status),
+            Error::Transport(err) => write!(f, "cannot reach the server: {}", err),
+            Error::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A logged in session on the puzzle site, caching inputs in a directory.
+pub struct Client {
+    agent: ureq::Agent,
+    base: String,
+    session: String,
+    year: u16,
+    inputs: PathBuf,
+}
+
+impl Client {
+    /// A client for [`BASE_URL`] with the given session token, caching inputs
+    /// in `inputs`.
+    pub fn new(session: &str, inputs: &Path) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base: BASE_URL.to_string(),
+            session: session.to_string(),
+            year: YEAR,
+            inputs: inputs.to_path_buf(),
+        }
+    }
+
+    /// A client with the session token from `AOC_SESSION` and the site from
+    /// `AOC_URL`, if set.
+    pub fn from_env(inputs: &Path) -> Result<Self, Error> {
+        let session = std::env::var(SESSION_ENV).map_err(|_| Error::NoSession)?;
+        let client = Self::new(session.trim(), inputs);
+
+        Ok(match std::env::var(URL_ENV) {
+            Ok(url) => client.with_base_url(&url),
+            Err(_) => client,
+        })
+    }
+
+    /// Sends requests to the site at `url` instead, e.g.
+    /// `http://127.0.0.1:8080`.
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Fetches puzzles from another year's event.
+    pub fn with_year(mut self, year: u16) -> Self {
+        self.year = year;
+        self
+    }
+
+    fn url(&self, day: u8, page: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.base, self.year, day, page)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Where the day's input is cached.
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        aoc_core::input::path(&self.inputs, day)
+    }
+
+    /// The day's input, fetched and cached unless it already is. Returns the
+    /// input and whether it had to be fetched.
+    pub fn input(&self, day: u8) -> Result<(String, bool), Error> {
+        let path = self.input_path(day);
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok((input, false)),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let input = self
+            .agent
+            .get(&self.url(day, "input"))
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+
+        // Written aside first, so an interrupted write never leaves a
+        // truncated input that would be taken for the real one.
+        fs::create_dir_all(&self.inputs)?;
+        let partial = path.with_extension("txt.part");
+        fs::write(&partial, &input)?;
+        fs::rename(&partial, &path)?;
+
+        Ok((input, true))
+    }
+
+    /// Every submission recorded so far, oldest first.
+    pub fn submissions(&self) -> Result<Vec<Submission>, Error> {
+        let file = match fs::File::open(self.inputs.join(SUBMISSIONS)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut submissions = vec![];
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if let Ok(submission) = serde_json::from_str(&line) {
+                submissions.push(submission);
+            }
+        }
+
+        Ok(submissions)
+    }
+
+    /// The final verdict recorded for this answer, if it was submitted before.
+    pub fn previous(&self, day: u8, part: u8, answer: &str) -> Result<Option<Verdict>, Error> {
+        Ok(self
+            .submissions()?
+            .into_iter()
+            .find(|s| s.day == day && s.part == part && s.answer == answer && s.verdict.is_final())
+            .map(|s| s.verdict))
+    }
+
+    /// Submits an answer to a part and records the verdict. See
+    /// [`Client::previous`] to avoid sending an answer twice.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let html = self
+            .agent
+            .post(&self.url(day, "answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+        let verdict = Verdict::parse(&html);
+
+        let submission = Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        };
+        fs::create_dir_all(&self.inputs)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.inputs.join(SUBMISSIONS))?;
+        let line = serde_json::to_string(&submission).expect("submissions serialize");
+        writeln!(file, "{}", line)?;
+
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use tiny_http::{Header, Response, Server};
+
+    /// A server on a free local port, stopped when dropped.
+    struct Mock {
+        url: String,
+        server: Arc<Server>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl Drop for Mock {
+        fn drop(&mut self) {
+            self.server.unblock();
+        }
+    }
+
+    /// Starts a server answering each request with `respond`. Requests
+    /// without the session cookie get a 400, like the real site.
+    fn serve(respond: fn(&str, &str) -> (u16, String)) -> Mock {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let (handle, count) = (Arc::clone(&server), Arc::clone(&requests));
+        thread::spawn(move || {
+            for mut request in handle.incoming_requests() {
+                count.fetch_add(1, Ordering::SeqCst);
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let authorised = request
+                    .headers()
+                    .iter()
+                    .any(|h| h.field.equiv("Cookie") && h.value == "session=token");
+
+                let (status, text) = if authorised {
+                    respond(request.url(), &body)
+                } else {
+                    (400, "Puzzle inputs differ by user.".into())
+                };
+                let html = Header::from_bytes("Content-Type", "text/html").unwrap();
+                let response = Response::from_string(text)
+                    .with_status_code(status)
+                    .with_header(html);
+                let _ = request.respond(response);
+            }
+        });
+
+        Mock {
+            url,
+            server,
+            requests,
+        }
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer. If you're stuck, ...")),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently. You have 4m 51s left to wait."
+            )),
+            Verdict::TooSoon {
+                wait: Some("4m 51s".into())
+            }
+        );
+        assert_eq!(
+            Verdict::parse(&page("You don't seem to be solving the right level.")),
+            Verdict::WrongLevel
+        );
+        assert_eq!(
+            Verdict::parse("<p>Server  error</p>"),
+            Verdict::Unrecognised("Server error".into())
+        );
+    }
+
+    #[test]
+    fn fetches_inputs_once() {
+        let mock = serve(|path, _| match path {
+            "/2022/day/1/input" => (200, "1000\n2000\n\n3000\n".into()),
+            _ => (404, "Not Found".into()),
+        });
+        let dir = scratch("inputs");
+        let client = Client::new("token", &dir).with_base_url(&format!("{}/", mock.url));
+
+        let first = client.input(1).unwrap();
+        let second = client.input(1).unwrap();
+        let cached = fs::read_to_string(dir.join("01.txt")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, ("1000\n2000\n\n3000\n".to_string(), true));
+        assert_eq!(second, (cached, false));
+        assert_eq!(mock.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn reports_refused_requests() {
+        let mock = serve(|_, _| (404, "Not Found".into()));
+        let dir = scratch("refused");
+
+        let missing = Client::new("token", &dir)
+            .with_base_url(&mock.url)
+            .input(25);
+        let unauthorised = Client::new("expired", &dir)
+            .with_base_url(&mock.url)
+            .input(1);
+
+        assert!(matches!(missing, Err(Error::Status { status: 404, .. })));
+        assert!(matches!(
+            unauthorised,
+            Err(Error::Status { status: 400, .. })
+        ));
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn records_submissions() {
+        let mock = serve(|path, body| {
+            assert_eq!(path, "/2022/day/1/answer");
+            let verdict = match body {
+                "level=1&answer=24000" => "That's the right answer!",
+                "level=2&answer=45000" => "That's not the right answer; your answer is too high.",
+                _ => "That's not the right answer.",
+            };
+            (200, format!("<article><p>{}</p></article>", verdict))
+        });
+        let dir = scratch("submissions");
+        let client = Client::new("token", &dir).with_base_url(&mock.url);
+
+        let part1 = client.submit(1, 1, "24000").unwrap();
+        let part2 = client.submit(1, 2, "45000").unwrap();
+        let previous = client.previous(1, 2, "45000").unwrap();
+        let unsent = client.previous(1, 2, "44999").unwrap();
+        let recorded = client.submissions().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!((part1, part2), (Verdict::Correct, Verdict::TooHigh));
+        assert_eq!((previous, unsent), (Some(Verdict::TooHigh), None));
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].answer, "24000");
+    }
+}
@@ -0,0 +1,146 @@
+//! Counting heap allocations, to see how much of a run is spent allocating.
+//!
+//! A binary opts in by installing [`Counting`] as its global allocator:
+//!
+//! ```
+//! #[global_allocator]
+//! static ALLOC: aoc_core::alloc::Counting = aoc_core::alloc::Counting;
+//!
+//! let (_, allocations) = aoc_core::alloc::measure(|| vec![0u8; 100]);
+//! assert_eq!(allocations.count, 1);
+//! assert_eq!(allocations.bytes, 100);
+//! ```
+//!
+//! Counts are kept per thread, so runs on other threads don't skew them.
+//! Without the allocator installed, every count is zero.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+/// Heap allocations made, including reallocations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: u64,
+    /// Total bytes requested; memory freed in between is not subtracted.
+    pub bytes: u64,
+}
+
+impl fmt::Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = ["B", "KiB", "MiB", "GiB"];
+        let mut size = self.bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < units.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        let plural = if self.count == 1 { "" } else { "s" };
+        if unit == 0 {
+            write!(f, "{} allocation{}, {} B", self.count, plural, self.bytes)
+        } else {
+            write!(
+                f,
+                "{} allocation{}, {:.1} {}",
+                self.count, plural, size, units[unit]
+            )
+        }
+    }
+}
+
+thread_local! {
+    static COUNTS: Cell<Allocations> = const {
+        Cell::new(Allocations { count: 0, bytes: 0 })
+    };
+}
+
+fn record(bytes: usize) {
+    // The thread's counts may already be gone while it shuts down.
+    let _ = COUNTS.try_with(|counts| {
+        let mut current = counts.get();
+        current.count += 1;
+        current.bytes += bytes as u64;
+        counts.set(current);
+    });
+}
+
+/// The system allocator, counting every allocation made on each thread.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations made on this thread so far.
+pub fn current() -> Allocations {
+    COUNTS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// Runs `f`, returning its result and the allocations it made on this thread.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let before = current();
+    let value = f();
+    let after = current();
+
+    (
+        value,
+        Allocations {
+            count: after.count - before.count,
+            bytes: after.bytes - before.bytes,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: Counting = Counting;
+
+    #[test]
+    fn counts_allocations() {
+        let (_, none) = measure(|| 1 + 1);
+        assert_eq!(none, Allocations::default());
+
+        let (_, grown) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(4);
+            v.extend([1, 2, 3, 4, 5]);
+            v
+        });
+        assert_eq!(
+            grown,
+            Allocations {
+                count: 2,
+                bytes: 32 + 64
+            }
+        );
+    }
+
+    #[test]
+    fn formats_sizes() {
+        let allocations = |count, bytes| Allocations { count, bytes }.to_string();
+
+        assert_eq!(allocations(1, 100), "1 allocation, 100 B");
+        assert_eq!(allocations(3, 1536), "3 allocations, 1.5 KiB");
+        assert_eq!(allocations(0, 0), "0 allocations, 0 B");
+    }
+}
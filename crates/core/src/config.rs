@@ -0,0 +1,289 @@
+//! Puzzle constants, such as day 07's disk size, that can be changed for
+//! variant puzzles or what-if runs without editing the solvers.
+//!
+//! Days read them from their own section of a TOML file, e.g. `[day07]`,
+//! into a typed [`Section`] whose fields default to the puzzle's values.
+//! The file is the one passed to [`Settings::load`], or else the one named
+//! by the [`ENV`] variable, or else [`FILE`] in the working directory if
+//! there is one. Overrides like `day07.disk_size=50000000` take precedence
+//! over the file.
+//!
+//! Each day's section is checked before the settings are installed, so the
+//! days read values that are known to be valid with [`get`].
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use serde::de::DeserializeOwned;
+use toml::{Table, Value};
+
+/// File read when no other is given, if it exists.
+pub const FILE: &str = "aoc.toml";
+
+/// Environment variable naming the file when none is passed explicitly.
+pub const ENV: &str = "AOC_CONFIG";
+
+/// A day's section of the configuration, whose default is the puzzle's own
+/// constants.
+pub trait Section: DeserializeOwned + Default + Clone + PartialEq + Send + Sync + 'static {
+    /// Checks that values valid on their own make sense together.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// A day's section once checked.
+struct Checked {
+    value: Box<dyn Any + Send + Sync>,
+    /// Whether it differs from the puzzle's constants.
+    custom: bool,
+}
+
+/// The sections installed by [`Settings::install`], by day.
+static INSTALLED: RwLock<Option<HashMap<u8, Checked>>> = RwLock::new(None);
+
+/// A configuration file and its overrides, whose sections are checked before
+/// the days can read them.
+#[derive(Default)]
+pub struct Settings {
+    table: Table,
+    checked: HashMap<u8, Checked>,
+}
+
+impl Settings {
+    /// Reads the configuration from `path`, the file in [`ENV`] or [`FILE`],
+    /// in that order, then applies `overrides` of the form `dayNN.key=value`.
+    pub fn load(path: Option<&Path>, overrides: &[String]) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => std::env::var_os(ENV).map(Into::into),
+        };
+        let mut table = match path {
+            Some(path) => read(&path)?,
+            None if Path::new(FILE).exists() => read(Path::new(FILE))?,
+            None => Table::new(),
+        };
+        for assignment in overrides {
+            set(&mut table, assignment)?;
+        }
+
+        Ok(Self {
+            table,
+            checked: HashMap::new(),
+        })
+    }
+
+    /// Names of the sections in the configuration, to spot ones no day reads.
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.table.keys().map(String::as_str)
+    }
+
+    /// Reads a day's section, or the puzzle's constants if it has none, and
+    /// checks it is a valid `C`.
+    pub fn check<C: Section>(&mut self, day: u8) -> Result<(), String> {
+        let config: C = section(&self.table, day)?;
+        self.checked.insert(
+            day,
+            Checked {
+                custom: config != C::default(),
+                value: Box::new(config),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Makes the checked sections the ones [`get`] returns. Days whose
+    /// section was not checked get the puzzle's constants.
+    pub fn install(self) {
+        *INSTALLED.write().unwrap_or_else(|err| err.into_inner()) = Some(self.checked);
+    }
+}
+
+fn read(path: &Path) -> Result<Table, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {} ({})", path.display(), err))?;
+
+    text.parse().map_err(|err: toml::de::Error| {
+        format!(
+            "invalid configuration in {} ({})",
+            path.display(),
+            err.to_string().trim_end()
+        )
+    })
+}
+
+/// Applies one `section.key=value` override. The value is read as TOML,
+/// or taken as a string if it is not valid TOML.
+fn set(table: &mut Table, assignment: &str) -> Result<(), String> {
+    let invalid = || format!("expected `dayNN.key=value`, got `{}`", assignment);
+    let (path, value) = assignment.split_once('=').ok_or_else(invalid)?;
+    let keys: Vec<&str> = path.trim().split('.').collect();
+    if keys.len() < 2 || keys.iter().any(|key| key.is_empty()) {
+        return Err(invalid());
+    }
+
+    let value = format!("value = {}", value)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut parsed| parsed.remove("value"))
+        .unwrap_or_else(|| Value::String(value.to_string()));
+
+    let (last, sections) = keys.split_last().ok_or_else(invalid)?;
+    let mut table = table;
+    for key in sections {
+        table = table
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| format!("cannot set `{}`, `{}` is not a table", path, key))?;
+    }
+    table.insert(last.to_string(), value);
+
+    Ok(())
+}
+
+/// Name of a day's section, e.g. `day07`.
+pub fn section_name(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+fn section<C: Section>(table: &Table, day: u8) -> Result<C, String> {
+    let name = section_name(day);
+    let invalid = |err: &dyn std::fmt::Display| {
+        let err = err.to_string();
+        format!(
+            "invalid [{}] configuration ({})",
+            name,
+            err.trim_end().replace('\n', " ")
+        )
+    };
+    let section = match table.get(&name) {
+        Some(Value::Table(section)) => section.clone(),
+        Some(_) => return Err(invalid(&"not a table")),
+        None => Table::new(),
+    };
+
+    let config: C = Value::Table(section)
+        .try_into()
+        .map_err(|err: toml::de::Error| invalid(&err))?;
+    config.validate().map_err(|err| invalid(&err))?;
+
+    Ok(config)
+}
+
+/// A day's installed section, or the puzzle's constants if there is none.
+pub fn get<C: Section>(day: u8) -> C {
+    let installed = INSTALLED.read().unwrap_or_else(|err| err.into_inner());
+    installed
+        .as_ref()
+        .and_then(|checked| checked.get(&day))
+        .and_then(|checked| checked.value.downcast_ref::<C>())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Whether the installed configuration changes any of a day's constants,
+/// so answers recorded for the puzzle no longer apply.
+pub fn customized(day: u8) -> bool {
+    let installed = INSTALLED.read().unwrap_or_else(|err| err.into_inner());
+    installed
+        .as_ref()
+        .and_then(|checked| checked.get(&day))
+        .is_some_and(|checked| checked.custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Config {
+        window: usize,
+        name: String,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                window: 4,
+                name: "packet".to_string(),
+            }
+        }
+    }
+
+    impl Section for Config {
+        fn validate(&self) -> Result<(), String> {
+            match self.window {
+                0 => Err("`window` must be at least 1".to_string()),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn overrides_file_values() {
+        let mut table: Table = "[day06]\nwindow = 14\nname = \"message\"".parse().unwrap();
+        set(&mut table, "day06.window=7").unwrap();
+        set(&mut table, "day06.name=start of packet").unwrap();
+
+        assert_eq!(
+            section::<Config>(&table, 6).unwrap(),
+            Config {
+                window: 7,
+                name: "start of packet".to_string()
+            }
+        );
+        assert_eq!(section::<Config>(&table, 3).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let mut table = Table::new();
+        assert!(set(&mut table, "window=7").is_err());
+        assert!(set(&mut table, "day06.window").is_err());
+
+        set(&mut table, "day06.windows=7").unwrap();
+        assert!(section::<Config>(&table, 6)
+            .unwrap_err()
+            .contains("unknown field `windows`"));
+
+        table.insert("day06".to_string(), Value::Integer(7));
+        assert!(set(&mut table, "day06.window=7")
+            .unwrap_err()
+            .contains("not a table"));
+        assert!(section::<Config>(&table, 6).is_err());
+    }
+
+    #[test]
+    fn validates_sections() {
+        let table: Table = "[day06]\nwindow = 0".parse().unwrap();
+
+        assert_eq!(
+            section::<Config>(&table, 6).unwrap_err(),
+            "invalid [day06] configuration (`window` must be at least 1)"
+        );
+    }
+
+    #[test]
+    fn installs_checked_sections() {
+        let mut settings = Settings::default();
+        set(&mut settings.table, "day98.window=7").unwrap();
+        set(&mut settings.table, "day99.window=0").unwrap();
+
+        assert!(settings.check::<Config>(99).is_err());
+        settings.check::<Config>(98).unwrap();
+        settings.check::<Config>(97).unwrap();
+        settings.install();
+
+        assert_eq!(get::<Config>(98).window, 7);
+        assert_eq!(get::<Config>(99), Config::default());
+        assert!(customized(98) && !customized(97) && !customized(99));
+    }
+}
@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::fmt;
+
+/// Malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Day whose parser rejected the input, once known.
+    pub day: Option<u8>,
+    /// Line of the offending text, counting from 1.
+    pub line: usize,
+    /// Column of the offending text within its line, counting from 1.
+    pub column: usize,
+    /// The offending text; empty when the input ended too early.
+    pub found: String,
+    /// Description of what the parser expected instead.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Reports `found` while parsing `input`, where `found` is a slice of
+    /// `input`. The line and column are worked out from where `found` sits.
+    pub fn at(input: &str, found: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, found);
+
+        Self {
+            day: None,
+            line,
+            column,
+            found: found.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Attributes the error to a day.
+    pub fn for_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Moves an error reported against a single line to line `line` of the
+    /// whole input, for parsers that read one line at a time.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+
+    /// Moves an error reported against `fragment` to its position within
+    /// `input`, for parsers that only see a slice of the whole input.
+    pub fn within(mut self, input: &str, fragment: &str) -> Self {
+        let (line, column) = position(input, fragment);
+
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+}
+
+/// Finds the line and column `fragment` starts at within `input`. Fragments
+/// that don't point into `input` are placed at its start.
+fn position(input: &str, fragment: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (fragment.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= input.len())
+        .unwrap_or(0);
+
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    (line, before[line_start..].chars().count() + 1)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {:02}, ", day)?;
+        }
+
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+
+        if self.found.is_empty() {
+            write!(f, "found end of line")
+        } else {
+            write!(f, "found `{}`", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_fragment() {
+        let input = "R 4\nU x\n";
+        let found = &input[6..7];
+
+        let err = ParseError::at(input, found, "a number of steps");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.found, "x");
+    }
+
+    #[test]
+    fn locates_end_of_input() {
+        let input = "abc\nde";
+        let err = ParseError::at(input, &input[input.len()..], "more input");
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected more input, found end of line"
+        );
+    }
+
+    #[test]
+    fn moves_fragment_errors_into_input() {
+        let input = "move 1 from 2 to 1\nmove x from 1 to 2";
+        let line = &input[19..];
+        let err = ParseError::at(line, &line[5..6], "a crate count").within(input, line);
+
+        assert_eq!((err.line, err.column), (2, 6));
+    }
+
+    #[test]
+    fn moves_line_errors_onto_their_line() {
+        let line = "U x";
+        let err = ParseError::at(line, &line[2..], "a number of steps").on_line(7);
+
+        assert_eq!((err.line, err.column), (7, 3));
+    }
+
+    #[test]
+    fn displays_day() {
+        let err = ParseError::at("X 4", &"X 4"[..1], "a direction").for_day(9);
+
+        assert_eq!(
+            err.to_string(),
+            "day 09, line 1, column 1: expected a direction, found `X`"
+        );
+    }
+}
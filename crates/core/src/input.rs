@@ -0,0 +1,148 @@
+//! Loading puzzle inputs and normalising them before they reach a parser.
+//!
+//! Inputs are looked up in order from an explicit path, from the conventional
+//! `inputs/NN.txt` file, and finally from stdin. Whatever the source, parsers
+//! only ever see normalised text: `\n` line endings, no blank lines before or
+//! after the puzzle, and no trailing newline.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Directory holding the puzzle inputs, relative to the working directory.
+pub const INPUTS_DIR: &str = "inputs";
+
+/// Conventional location of a day's puzzle input within `dir`, e.g.
+/// `inputs/07.txt`.
+pub fn path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("{:02}.txt", day))
+}
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// The explicit `path` if given, otherwise the day's file in `dir` if it
+    /// exists, otherwise stdin.
+    pub fn find(path: Option<&Path>, dir: &Path, day: u8) -> Self {
+        match path {
+            Some(path) => Source::File(path.to_path_buf()),
+            None => {
+                let path = self::path(dir, day);
+                if path.is_file() {
+                    Source::File(path)
+                } else {
+                    Source::Stdin
+                }
+            }
+        }
+    }
+
+    /// Reads the whole input, as is.
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::File(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Opens the input to be read a line at a time.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Source::File(path) => Box::new(BufReader::new(File::open(path)?)),
+            Source::Stdin => Box::new(io::stdin().lock()),
+        })
+    }
+}
+
+/// Reads a day's input the way every binary does: from the path given as the
+/// first argument, from `inputs/NN.txt`, or from stdin.
+pub fn load(day: u8) -> io::Result<String> {
+    source(day).read()
+}
+
+/// Opens a day's input like [`load`], to be read a line at a time.
+pub fn open(day: u8) -> io::Result<Box<dyn BufRead>> {
+    source(day).open()
+}
+
+fn source(day: u8) -> Source {
+    let path = std::env::args_os().nth(1).map(PathBuf::from);
+    Source::find(path.as_deref(), Path::new(INPUTS_DIR), day)
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Splits `raw` into lines ending in `\n`, `\r\n` or a lone `\r`.
+fn lines(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split('\n')
+        .flat_map(|line| line.strip_suffix('\r').unwrap_or(line).split('\r'))
+}
+
+/// Number of blank lines [`normalize`] drops from the start of `raw`, used to
+/// report errors against the original line numbers. Input that is blank
+/// throughout has no line to report against, so nothing is dropped before it.
+pub fn leading_blank_lines(raw: &str) -> usize {
+    lines(raw).position(|line| !is_blank(line)).unwrap_or(0)
+}
+
+/// Converts `\r\n` and `\r` line endings to `\n`, and drops blank lines from
+/// both ends along with the final line ending. Leading whitespace on the first
+/// non-blank line is kept, as it can be part of the puzzle.
+pub fn normalize(raw: &str) -> String {
+    let lines: Vec<&str> = lines(raw).collect();
+
+    let start = lines.iter().position(|line| !is_blank(line));
+    let end = lines.iter().rposition(|line| !is_blank(line));
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n\r\nc\r\n"), "a\nb\n\nc");
+        assert_eq!(normalize("a\rb"), "a\nb");
+    }
+
+    #[test]
+    fn drops_surrounding_blank_lines() {
+        assert_eq!(normalize("\n\n  \n    [D]\n1\n\n \n"), "    [D]\n1");
+        assert_eq!(normalize("\n \r\n"), "");
+        assert_eq!(leading_blank_lines("\n \r\n  x\n"), 2);
+        assert_eq!(leading_blank_lines("x\n\n"), 0);
+        assert_eq!(leading_blank_lines(""), 0);
+        assert_eq!(leading_blank_lines("\n\n"), 0);
+        assert_eq!(leading_blank_lines("\r\rX"), 2);
+        assert_eq!(normalize("\r\rX"), "X");
+    }
+
+    #[test]
+    fn finds_inputs() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let explicit = Path::new("puzzle.txt");
+
+        assert_eq!(
+            Source::find(Some(explicit), &dir, 7),
+            Source::File(explicit.into())
+        );
+        assert_eq!(Source::find(None, &dir, 7), Source::Stdin);
+        assert_eq!(path(&dir, 7), dir.join("07.txt"));
+        assert_eq!(path(&dir, 11), dir.join("11.txt"));
+    }
+}
@@ -0,0 +1,238 @@
+//! Interface shared by every day's solution, so days can be run, composed and
+//! tested the same way.
+
+use std::fmt;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+pub mod alloc;
+pub mod config;
+mod error;
+pub mod input;
+pub mod log;
+mod stream;
+
+pub use alloc::Allocations;
+pub use error::ParseError;
+pub use serde_json::{json, Value};
+pub use stream::{solve_stream, Line, Lines, StreamError, Streaming};
+
+/// A day's puzzle, split into parsing the input and solving both parts.
+pub trait Solution {
+    /// Day of the advent calendar the puzzle was released on.
+    const DAY: u8;
+    /// Puzzle title, as published.
+    const TITLE: &'static str;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+    /// Answer to the first part.
+    type Part1: Display;
+    /// Answer to the second part.
+    type Part2: Display;
+
+    /// Parses the puzzle input, already passed through [`input::normalize`].
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Solves the first part of the puzzle.
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    /// Solves the second part of the puzzle.
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Auxiliary data behind the answers, such as the candidates a part chose
+    /// between. Nothing by default.
+    fn details(_input: &Self::Input) -> Value {
+        Value::Null
+    }
+}
+
+/// Rendered answers to both parts of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_part(f, 1, &self.part1)?;
+        write_part(f, 2, &self.part2)
+    }
+}
+
+fn write_part(f: &mut fmt::Formatter<'_>, part: u8, answer: &str) -> fmt::Result {
+    // Multi-line answers, like rendered images, start on their own line so
+    // they stay aligned.
+    if answer.contains('\n') {
+        writeln!(f, "Part {} -\n{}", part, answer)
+    } else {
+        writeln!(f, "Part {} - {}", part, answer)
+    }
+}
+
+/// Wall time spent in each step of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Heap allocations made in each step of a run. Only counted when the binary
+/// installs [`alloc::Counting`] as its allocator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocated {
+    pub parse: Allocations,
+    pub part1: Allocations,
+    pub part2: Allocations,
+}
+
+/// Everything a run of a puzzle produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub answers: Answers,
+    pub timings: Timings,
+    pub allocated: Allocated,
+    /// See [`Solution::details`].
+    pub details: Value,
+}
+
+/// Runs `f`, measuring the time it takes and the allocations it makes.
+fn measured<T>(f: impl FnOnce() -> T) -> (T, Duration, Allocations) {
+    let start = Instant::now();
+    let (value, allocations) = alloc::measure(f);
+
+    (value, start.elapsed(), allocations)
+}
+
+/// Normalises and parses the input, reporting errors against the original
+/// input and attributing them to the day.
+pub fn parse<S: Solution>(input: &str) -> Result<S::Input, ParseError> {
+    S::parse(&input::normalize(input)).map_err(|mut err| {
+        // Errors point into the normalised input; count the blank lines it
+        // dropped so they match the original file.
+        err.line += input::leading_blank_lines(input);
+        err.for_day(S::DAY)
+    })
+}
+
+/// Normalises and parses the input once, then solves both parts of the
+/// puzzle, timing each step and counting its allocations. Rendering the
+/// answers is counted as part of solving them.
+pub fn run<S: Solution>(input: &str) -> Result<Run, ParseError> {
+    let (parsed, parse_time, parse_allocs) = measured(|| parse::<S>(input));
+    let parsed = parsed?;
+    let (part1, part1_time, part1_allocs) = measured(|| S::part1(&parsed).to_string());
+    let (part2, part2_time, part2_allocs) = measured(|| S::part2(&parsed).to_string());
+
+    Ok(Run {
+        answers: Answers { part1, part2 },
+        timings: Timings {
+            parse: parse_time,
+            part1: part1_time,
+            part2: part2_time,
+        },
+        allocated: Allocated {
+            parse: parse_allocs,
+            part1: part1_allocs,
+            part2: part2_allocs,
+        },
+        details: S::details(&parsed),
+    })
+}
+
+/// Parses the input once and solves both parts of the puzzle.
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, ParseError> {
+    run::<S>(input).map(|run| run.answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Line Lengths";
+
+        type Input = Vec<usize>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .lines()
+                .map(|line| {
+                    if line.starts_with(' ') {
+                        return Err(ParseError::at(input, line, "no leading spaces"));
+                    }
+
+                    Ok(line.len())
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Part1 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Part2 {
+            input
+                .iter()
+                .map(|len| "#".repeat(*len))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn solves_both_parts() {
+        assert_eq!(
+            solve::<Lengths>("ab\nc"),
+            Ok(Answers {
+                part1: "3".into(),
+                part2: "##\n#".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn attributes_errors_to_day() {
+        let err = solve::<Lengths>("ab\n c").unwrap_err();
+
+        assert_eq!(err.day, Some(0));
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn normalizes_input() {
+        assert_eq!(
+            solve::<Lengths>("\r\nab\r\nc\r\n\n"),
+            solve::<Lengths>("ab\nc")
+        );
+
+        let err = solve::<Lengths>("\n\nab\r\n c").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+
+        let err = solve::<Lengths>("\r\r ab").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+
+    #[test]
+    fn runs_without_details() {
+        let run = run::<Lengths>("ab\nc").unwrap();
+
+        assert_eq!(run.answers.part1, "3");
+        assert_eq!(run.details, Value::Null);
+    }
+
+    #[test]
+    fn displays_answers() {
+        assert_eq!(
+            solve::<Lengths>("ab\nc").unwrap().to_string(),
+            "Part 1 - 3\nPart 2 -\n##\n#\n"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Diagnostics from the solvers, such as each cycle of day 10's CPU.
+//!
+//! Solvers emit [`tracing`](https://docs.rs/tracing) spans and events, which
+//! are dropped unless a filter enables them. Filters use `RUST_LOG` syntax,
+//! e.g. `aoc_10=trace` or `debug`, and come from a `--log` flag or the
+//! [`ENV`] variable. Output goes to stderr so the answers on stdout stay clean.
+
+use std::io::{self, IsTerminal};
+
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding the filter when none is passed explicitly.
+pub const ENV: &str = "AOC_LOG";
+
+/// Writes events matching `filter`, or the filter in [`ENV`] if `filter` is
+/// `None`, to stderr. Does nothing if neither is set.
+pub fn init(filter: Option<&str>) -> Result<(), String> {
+    let filter = match filter {
+        Some(filter) => filter.to_string(),
+        None => match std::env::var(ENV) {
+            Ok(filter) => filter,
+            Err(_) => return Ok(()),
+        },
+    };
+
+    let filter = EnvFilter::try_new(&filter)
+        .map_err(|err| format!("invalid log filter `{}` ({})", filter, err))?;
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .try_init()
+        .map_err(|err| format!("cannot install logger ({})", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_filters() {
+        assert!(init(Some("aoc_10=loud"))
+            .unwrap_err()
+            .contains("aoc_10=loud"));
+    }
+}
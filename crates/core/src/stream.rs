@@ -0,0 +1,167 @@
+//! Solving puzzles while reading their input, for days that only need a line
+//! or a small group of lines at a time. Memory then depends on the day's
+//! working state rather than on the size of the input.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{Answers, ParseError, Solution};
+
+/// A solution that can also solve both parts from a stream of lines.
+pub trait Streaming: Solution {
+    /// Solves both parts while reading the input one line at a time.
+    fn stream(lines: Lines<'_>) -> Result<(Self::Part1, Self::Part2), StreamError>;
+}
+
+/// Failure to solve a streamed input.
+#[derive(Debug)]
+pub enum StreamError {
+    /// The input could not be read.
+    Io(io::Error),
+    /// The input was malformed.
+    Parse(ParseError),
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        StreamError::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> Self {
+        StreamError::Parse(err)
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "cannot read input: {}", err),
+            StreamError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(err) => Some(err),
+            StreamError::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// A line of input along with its line number, counting from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub number: usize,
+    pub text: String,
+}
+
+/// Lines read from the input, normalised as [`crate::input::normalize`] does:
+/// `\r\n` endings are stripped and blank lines at either end are dropped.
+/// Blank lines are held back until a non-blank line follows them, so only a
+/// run of blank lines is ever buffered.
+pub struct Lines<'a> {
+    reader: &'a mut dyn BufRead,
+    number: usize,
+    started: bool,
+    blanks: Vec<Line>,
+    ready: VecDeque<Line>,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(reader: &'a mut dyn BufRead) -> Self {
+        Self {
+            reader,
+            number: 0,
+            started: false,
+            blanks: vec![],
+            ready: VecDeque::new(),
+        }
+    }
+}
+
+impl Iterator for Lines<'_> {
+    type Item = io::Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(line) = self.ready.pop_front() {
+                return Some(Ok(line));
+            }
+
+            let mut text = String::new();
+            match self.reader.read_line(&mut text) {
+                Ok(0) => return None,
+                Ok(_) => (),
+                Err(err) => return Some(Err(err)),
+            }
+
+            if text.ends_with('\n') {
+                text.pop();
+                if text.ends_with('\r') {
+                    text.pop();
+                }
+            }
+
+            self.number += 1;
+            let line = Line {
+                number: self.number,
+                text,
+            };
+
+            if line.text.trim().is_empty() {
+                if self.started {
+                    self.blanks.push(line);
+                }
+                continue;
+            }
+
+            self.started = true;
+            self.ready.extend(self.blanks.drain(..));
+            self.ready.push_back(line);
+        }
+    }
+}
+
+/// Solves both parts of the puzzle while reading its input from `reader`.
+pub fn solve_stream<S: Streaming>(reader: &mut dyn BufRead) -> Result<Answers, StreamError> {
+    match S::stream(Lines::new(reader)) {
+        Ok((part1, part2)) => Ok(Answers {
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+        }),
+        Err(StreamError::Parse(err)) => Err(StreamError::Parse(err.for_day(S::DAY))),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::normalize;
+
+    fn read(input: &str) -> Vec<(usize, String)> {
+        let mut reader = input.as_bytes();
+        Lines::new(&mut reader)
+            .map(|line| line.map(|line| (line.number, line.text)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn normalizes_lines() {
+        assert_eq!(
+            read("\r\n \n  a\r\n\r\nb\n \n\n"),
+            vec![(3, "  a".into()), (4, "".into()), (5, "b".into())]
+        );
+
+        for input in ["a\r\n\r\n b\r\n", "\n\na\n\n\n", ""] {
+            let lines: Vec<String> = read(input).into_iter().map(|(_, text)| text).collect();
+            assert_eq!(lines.join("\n"), normalize(input));
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Seeded generators of synthetic puzzle input, in each day's exact format and
+//! at any scale. The same seed always produces the same input.
+//!
+//! ```
+//! let input = aoc_gen::generate(2, 3, 7).unwrap();
+//! assert_eq!(input, aoc_gen::generate(2, 3, 7).unwrap());
+//! assert_eq!(input.lines().count(), 3);
+//! ```
+
+use std::io::{self, Write};
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// The random number generator every generator draws from; its output is
+/// stable across platforms and releases.
+pub type Rand = rand_chacha::ChaCha8Rng;
+
+/// Writes `size` records of a day's puzzle input.
+pub type Generator = fn(&mut Rand, usize, &mut dyn Write) -> io::Result<()>;
+
+/// The generator for a day, if there is one.
+pub fn generator(day: u8) -> Option<Generator> {
+    let generator: Generator = match day {
+        1 => calories,
+        2 => strategy_guide,
+        3 => rucksacks,
+        4 => assignments,
+        5 => supply_stacks,
+        6 => datastream,
+        7 => shell_transcript,
+        8 => forest,
+        9 => rope_motions,
+        10 => program,
+        11 => monkeys,
+        _ => return None,
+    };
+
+    Some(generator)
+}
+
+/// Runs the generator with the given size and seed, writing to `out`.
+pub fn write(generator: Generator, size: usize, seed: u64, out: &mut dyn Write) -> io::Result<()> {
+    generator(&mut Rand::seed_from_u64(seed), size, out)
+}
+
+/// Generates a day's input of the given size and seed in memory.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let mut out = vec![];
+    write(generator(day)?, size, seed, &mut out).expect("writing to a Vec cannot fail");
+
+    Some(String::from_utf8(out).expect("generators write ASCII"))
+}
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Day 01: `size` elves, each carrying a handful of snacks.
+pub fn calories(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for elf in 0..size.max(1) {
+        if elf > 0 {
+            writeln!(out)?;
+        }
+
+        for _ in 0..rng.random_range(1..=6) {
+            writeln!(out, "{}", rng.random_range(1000..=70_000))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Day 02: `size` rounds of rock paper scissors.
+pub fn strategy_guide(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let expected = ['A', 'B', 'C'][rng.random_range(0..3)];
+        let strategy = ['X', 'Y', 'Z'][rng.random_range(0..3)];
+        writeln!(out, "{} {}", expected, strategy)?;
+    }
+
+    Ok(())
+}
+
+/// Day 03: `size` rucksacks, rounded up to whole groups of three. Each
+/// rucksack has a single item type in both compartments and each group a
+/// single badge carried by all three elves.
+pub fn rucksacks(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size.div_ceil(3) {
+        let mut letters = LETTERS.to_vec();
+        letters.shuffle(rng);
+        let (badge, pools) = letters.split_first().unwrap();
+
+        // Every elf draws from its own 17 item types, so only the badge is
+        // shared by the whole group.
+        for pool in pools.chunks(17) {
+            let (misplaced, pool) = pool.split_first().unwrap();
+            let (front_pool, back_pool) = pool.split_at(pool.len() / 2);
+            let half = rng.random_range(2..=16);
+
+            let mut front = vec![*misplaced];
+            let mut back = vec![*misplaced, *badge];
+            front.extend((1..half).map(|_| front_pool[rng.random_range(0..front_pool.len())]));
+            back.extend((2..half).map(|_| back_pool[rng.random_range(0..back_pool.len())]));
+            front.shuffle(rng);
+            back.shuffle(rng);
+
+            front.extend(back);
+            writeln!(out, "{}", String::from_utf8(front).unwrap())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Day 04: `size` pairs of section assignments.
+pub fn assignments(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let mut range = || {
+            let start = rng.random_range(1..=99);
+            (start, rng.random_range(start..=99))
+        };
+        let (a, b) = range();
+        let (c, d) = range();
+        writeln!(out, "{}-{},{}-{}", a, b, c, d)?;
+    }
+
+    Ok(())
+}
+
+/// Day 05: a drawing of nine stacks followed by `size` moves, none of which
+/// takes more crates than the stack holds.
+pub fn supply_stacks(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const STACKS: usize = 9;
+
+    let mut heights: Vec<usize> = (0..STACKS).map(|_| rng.random_range(1..=8)).collect();
+
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let row = heights
+            .iter()
+            .map(|height| match *height > level {
+                true => format!("[{}]", rng.random_range(b'A'..=b'Z') as char),
+                false => "   ".to_string(),
+            })
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", row.join(" "))?;
+    }
+
+    let numbers = (1..=STACKS).map(|i| format!(" {} ", i)).collect::<Vec<_>>();
+    writeln!(out, "{}", numbers.join(" "))?;
+    writeln!(out)?;
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.random_range(0..STACKS);
+            if heights[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + rng.random_range(1..STACKS)) % STACKS;
+        let count = rng.random_range(1..=heights[from]);
+
+        heights[from] -= count;
+        heights[to] += count;
+        writeln!(out, "move {} from {} to {}", count, from + 1, to + 1)?;
+    }
+
+    Ok(())
+}
+
+/// Day 06: a datastream of `size` characters, at least 14, whose
+/// start-of-message marker only appears at the very end.
+pub fn datastream(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut marker = LETTERS[..26].to_vec();
+    marker.shuffle(rng);
+    let marker = &mut marker[..14];
+    let first = marker.iter().position(|c| *c <= b'm').unwrap();
+    marker.swap(0, first);
+
+    // Thirteen letters can never hold fourteen distinct characters, and the
+    // run of the marker's first letter leading into it keeps the marker from
+    // completing early.
+    let mut noise = (0..size.saturating_sub(14))
+        .map(|_| rng.random_range(b'a'..=b'm'))
+        .collect::<Vec<_>>();
+    let run = noise.len().saturating_sub(13);
+    noise[run..].fill(marker[0]);
+
+    out.write_all(&noise)?;
+    out.write_all(&marker[..14])?;
+    writeln!(out)
+}
+
+/// Day 07: a transcript exploring `size` directories. The files add up to
+/// between 45,000,000 and 65,000,000 bytes, so the 70,000,000 byte disk can
+/// always be cleaned up for the update.
+pub fn shell_transcript(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.max(1);
+
+    // Directory 0 is the root; every other directory hangs off an earlier one.
+    let mut children = vec![vec![]; size];
+    for dir in 1..size {
+        children[rng.random_range(0..dir)].push(dir);
+    }
+
+    let mut weights = (0..size)
+        .map(|_| {
+            (0..rng.random_range(0..=4))
+                .map(|_| rng.random_range(1..=1000u64))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    weights[0].push(rng.random_range(1..=1000));
+
+    let total = rng.random_range(45_000_000..=65_000_000u64);
+    let weight = weights.iter().flatten().sum::<u64>();
+    let files = weights
+        .iter()
+        .map(|dir| {
+            dir.iter()
+                .map(|w| (w * total / weight).max(1))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    fn visit(
+        dir: usize,
+        children: &[Vec<usize>],
+        files: &[Vec<u64>],
+        out: &mut dyn Write,
+    ) -> io::Result<()> {
+        writeln!(out, "$ ls")?;
+        for child in &children[dir] {
+            writeln!(out, "dir d{}", child)?;
+        }
+        for (i, size) in files[dir].iter().enumerate() {
+            writeln!(out, "{} f{}.dat", size, i)?;
+        }
+
+        for child in &children[dir] {
+            writeln!(out, "$ cd d{}", child)?;
+            visit(*child, children, files, out)?;
+            writeln!(out, "$ cd ..")?;
+        }
+
+        Ok(())
+    }
+
+    writeln!(out, "$ cd /")?;
+    visit(0, &children, &files, out)
+}
+
+/// Day 08: a `size` by `size` forest of tree heights.
+pub fn forest(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.max(1);
+    for _ in 0..size {
+        let row = (0..size)
+            .map(|_| rng.random_range(b'0'..=b'9'))
+            .collect::<Vec<_>>();
+        out.write_all(&row)?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Day 09: `size` motions of the rope's head.
+pub fn rope_motions(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let direction = ['U', 'D', 'L', 'R'][rng.random_range(0..4)];
+        writeln!(out, "{} {}", direction, rng.random_range(1..=20))?;
+    }
+
+    Ok(())
+}
+
+/// Day 10: a program of `size` instructions that keeps the sprite on screen.
+pub fn program(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut x: i32 = 1;
+    for _ in 0..size {
+        if rng.random_bool(0.3) {
+            writeln!(out, "noop")?;
+            continue;
+        }
+
+        let mut value = rng.random_range(-8..=8);
+        if !(0..40).contains(&(x + value)) {
+            value = -value;
+        }
+
+        x += value;
+        writeln!(out, "addx {}", value)?;
+    }
+
+    Ok(())
+}
+
+/// Day 11: `size` monkeys, at least two.
+///
+/// Worry levels must fit an `i64` through twenty rounds with relief, so
+/// operations never square the level and monkeys only throw to monkeys that
+/// have already had their turn, monkey 0 aside. Divisors are small primes,
+/// keeping their least common multiple small too.
+pub fn monkeys(rng: &mut Rand, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const PRIMES: [i64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+    let size = size.max(2);
+
+    for monkey in 0..size {
+        if monkey > 0 {
+            writeln!(out)?;
+        }
+
+        let items = (0..rng.random_range(0..=6))
+            .map(|_| rng.random_range(50..=99).to_string())
+            .collect::<Vec<_>>();
+        let operation = match rng.random_range(0..3) {
+            0 => format!("old * {}", rng.random_range(2..=7)),
+            1 => format!("old + {}", rng.random_range(1..=8)),
+            _ => "old + old".to_string(),
+        };
+        let targets = match monkey {
+            0 => 1..size,
+            _ => 0..monkey,
+        };
+        let divisor = PRIMES[rng.random_range(0..PRIMES.len())];
+        let pass = rng.random_range(targets.clone());
+        let fail = rng.random_range(targets);
+
+        writeln!(out, "Monkey {}:", monkey)?;
+        writeln!(out, "  Starting items: {}", items.join(", "))?;
+        writeln!(out, "  Operation: new = {}", operation)?;
+        writeln!(out, "  Test: divisible by {}", divisor)?;
+        writeln!(out, "    If true: throw to monkey {}", pass)?;
+        writeln!(out, "    If false: throw to monkey {}", fail)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Solution;
+
+    fn solves<S: Solution>(size: usize) {
+        for seed in 0..4 {
+            let input = generate(S::DAY, size, seed).unwrap();
+            aoc_core::run::<S>(&input).unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+        }
+    }
+
+    #[test]
+    fn generates_valid_inputs() {
+        solves::<aoc_01::Day01>(1000);
+        solves::<aoc_02::Day02>(1000);
+        solves::<aoc_03::Day03>(1000);
+        solves::<aoc_04::Day04>(1000);
+        solves::<aoc_05::Day05>(1000);
+        solves::<aoc_06::Day06>(1000);
+        solves::<aoc_07::Day07>(1000);
+        solves::<aoc_08::Day08>(30);
+        solves::<aoc_09::Day09>(1000);
+        solves::<aoc_10::Day10>(1000);
+        solves::<aoc_11::Day11>(20);
+    }
+
+    #[test]
+    fn is_reproducible() {
+        for day in 1..=11 {
+            assert_eq!(generate(day, 50, 1), generate(day, 50, 1));
+            assert_ne!(generate(day, 50, 1), generate(day, 50, 2));
+        }
+
+        assert!(generate(12, 50, 1).is_none());
+    }
+
+    #[test]
+    fn hides_the_message_marker_at_the_end() {
+        let input = generate(6, 500, 3).unwrap();
+
+        assert_eq!(
+            aoc_core::solve::<aoc_06::Day06>(&input).unwrap().part2,
+            "500"
+        );
+    }
+}
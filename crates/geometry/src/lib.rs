@@ -0,0 +1,306 @@
+//! Points, directions and distances on an integer plane.
+//!
+//! Coordinates follow the screen: `x` grows to the right and `y` grows down,
+//! matching the rows of a grid.
+//!
+//! ```
+//! use aoc_geometry::{Direction, Point};
+//!
+//! let head = Point::ORIGIN + Direction::Right.step() * 2 + Direction::Up.step();
+//! assert_eq!(head, Point::new(2, -1));
+//! assert_eq!(head.signum(), Point::new(1, -1));
+//! assert_eq!(head.manhattan(Point::ORIGIN), 3);
+//! assert_eq!(head.chebyshev(Point::ORIGIN), 2);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use aoc_core::ParseError;
+
+/// A point, or the vector between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    /// The point `(0, 0)`.
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// The sign of each coordinate, turning a vector into a single step
+    /// towards it, diagonals included.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Clamps each coordinate between `min` and `max`.
+    pub fn clamp(self, min: i32, max: i32) -> Self {
+        Self::new(self.x.clamp(min, max), self.y.clamp(min, max))
+    }
+
+    /// Taxicab distance: the number of orthogonal steps between the points.
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Chessboard distance: the number of steps between the points when
+    /// diagonal steps are allowed.
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// The point one step away in every direction, diagonals included.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Compass::ALL
+            .into_iter()
+            .map(move |heading| self + heading.step())
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i32) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// A single step in this direction.
+    pub const fn step(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// The direction after a quarter turn clockwise.
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// The direction after a quarter turn anticlockwise.
+    pub const fn turn_left(self) -> Self {
+        self.turn_right().turn_right().turn_right()
+    }
+
+    /// The direction after a half turn.
+    pub const fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+impl TryFrom<&str> for Direction {
+    type Error = ParseError;
+
+    /// Reads `U`, `R`, `D` or `L`.
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Ok(match input {
+            "U" => Direction::Up,
+            "R" => Direction::Right,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            _ => return Err(ParseError::at(input, input, "`U`, `D`, `L` or `R`")),
+        })
+    }
+}
+
+impl fmt::Display for Direction {
+    /// Writes the direction's letter, as read by its `TryFrom<&str>`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letter = match self {
+            Direction::Up => "U",
+            Direction::Right => "R",
+            Direction::Down => "D",
+            Direction::Left => "L",
+        };
+
+        write!(f, "{}", letter)
+    }
+}
+
+/// One of the eight compass headings, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass {
+    /// Every heading, clockwise from north.
+    pub const ALL: [Compass; 8] = [
+        Compass::N,
+        Compass::NE,
+        Compass::E,
+        Compass::SE,
+        Compass::S,
+        Compass::SW,
+        Compass::W,
+        Compass::NW,
+    ];
+
+    /// A single step in this heading, with north pointing up.
+    pub const fn step(self) -> Point {
+        match self {
+            Compass::N => Point::new(0, -1),
+            Compass::NE => Point::new(1, -1),
+            Compass::E => Point::new(1, 0),
+            Compass::SE => Point::new(1, 1),
+            Compass::S => Point::new(0, 1),
+            Compass::SW => Point::new(-1, 1),
+            Compass::W => Point::new(-1, 0),
+            Compass::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Compass::N,
+            Direction::Right => Compass::E,
+            Direction::Down => Compass::S,
+            Direction::Left => Compass::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_vector_arithmetic() {
+        let mut p = Point::new(3, -2);
+
+        p += Point::new(1, 1);
+        assert_eq!(p, Point::new(4, -1));
+        p -= Point::new(4, 0);
+        assert_eq!(p, Point::new(0, -1));
+        assert_eq!(-p * 3, Point::new(0, 3));
+        assert_eq!(Point::new(7, -9).clamp(-1, 1), Point::new(1, -1));
+        assert_eq!(Point::new(0, -9).signum(), Point::new(0, -1));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(-1, 2);
+        let b = Point::new(3, -5);
+
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.manhattan(a), 0);
+        assert!(a.neighbours().all(|n| n.chebyshev(a) == 1));
+        assert_eq!(a.neighbours().filter(|n| n.manhattan(a) == 1).count(), 4);
+    }
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.opposite().step(), -direction.step());
+            assert_eq!(Compass::from(direction).step(), direction.step());
+        }
+
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(Direction::try_from("L").unwrap(), Direction::Left);
+        for direction in Direction::ALL {
+            assert_eq!(
+                Direction::try_from(direction.to_string().as_str()),
+                Ok(direction)
+            );
+        }
+
+        let err = Direction::try_from("X").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (1, "X"));
+    }
+}
@@ -0,0 +1,334 @@
+//! A dense, rectangular grid for the days whose input is a map.
+//!
+//! Cells are addressed by `(x, y)`, with `x` counting columns from the left and
+//! `y` counting rows from the top.
+//!
+//! ```
+//! use aoc_grid::Grid;
+//!
+//! let grid = Grid::parse("123\n456", "a map of digits", "a digit", |c| c.to_digit(10)).unwrap();
+//! assert_eq!(grid.get((2, 1)), Some(&6));
+//! assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+//! assert_eq!(grid.render(|n| char::from_digit(*n, 10).unwrap()), "123\n456");
+//! ```
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use aoc_core::ParseError;
+use aoc_geometry::{Compass, Direction, Point};
+
+/// Position of a cell, as `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// Steps to the four orthogonal neighbours: up, right, down and left.
+pub const ORTHOGONAL: [Point; 4] = [
+    Direction::Up.step(),
+    Direction::Right.step(),
+    Direction::Down.step(),
+    Direction::Left.step(),
+];
+
+/// Steps to all eight neighbours, clockwise from up.
+pub const ADJACENT: [Point; 8] = [
+    Compass::N.step(),
+    Compass::NE.step(),
+    Compass::E.step(),
+    Compass::SE.step(),
+    Compass::S.step(),
+    Compass::SW.step(),
+    Compass::W.step(),
+    Compass::NW.step(),
+];
+
+/// A `width` by `height` grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows. Returns `None` if the rows differ in
+    /// length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map(Vec::len).unwrap_or(0);
+        let height = rows.len();
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, reading each character with
+    /// `cell`. Characters `cell` rejects are reported as not being `expected`,
+    /// and an input without any cells as not being `map`.
+    pub fn parse(
+        input: &str,
+        map: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in input.lines() {
+            let before = cells.len();
+            for (i, c) in line.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], expected))?;
+                cells.push(value);
+            }
+
+            let row = cells.len() - before;
+            match width {
+                None => width = Some(row),
+                Some(width) if width != row => {
+                    return Err(ParseError::at(
+                        input,
+                        line,
+                        format!("a row {} cells wide", width),
+                    ));
+                }
+                Some(_) => (),
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseError::at(input, input, map)),
+        }
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` lies within the grid.
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The cell at `pos`, if it lies within the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    /// The cell at `pos`, if it lies within the grid.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.1 * self.width + pos.0])
+    }
+
+    /// The cells of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Every row, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The position one `step` away from `pos`, if it lies within the grid.
+    pub fn step(&self, (x, y): Pos, step: Point) -> Option<Pos> {
+        let pos = (
+            x.checked_add_signed(step.x as isize)?,
+            y.checked_add_signed(step.y as isize)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Positions next to `pos` in the given directions, such as
+    /// [`ORTHOGONAL`] or [`ADJACENT`], skipping those off the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        steps: &'a [Point],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        steps.iter().filter_map(move |step| self.step(pos, *step))
+    }
+
+    /// Positions from `pos`, exclusive, repeatedly moving by `step` until the
+    /// edge of the grid.
+    pub fn ray(&self, pos: Pos, step: Point) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, step), move |pos| self.step(*pos, step))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Renders the grid as text, one line per row, drawing each cell with `f`.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, "a map of digits", "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = digits("123\n456");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid[(0, 1)], 4);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]),
+            Some(grid)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_maps() {
+        let parse = |input| {
+            Grid::parse(input, "a map of digits", "a digit", |c| c.to_digit(10)).unwrap_err()
+        };
+
+        let err = parse("123\n4x6");
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+
+        let err = parse("123\n45");
+        assert_eq!((err.line, err.column), (2, 1));
+
+        assert_eq!(parse("").expected, "a map of digits");
+        assert!(Grid::from_rows(vec![vec![1], vec![]]).is_none());
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = Grid::new(2, 2, false);
+
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get_mut((0, 2)), None);
+        *grid.get_mut((1, 1)).unwrap() = true;
+        assert_eq!(grid.render(|on| if *on { '#' } else { '.' }), "..\n.#");
+    }
+
+    #[test]
+    fn walks_neighbours_and_rays() {
+        let grid = digits("123\n456\n789");
+
+        let around = |pos, steps| {
+            grid.neighbours(pos, steps)
+                .map(|p| grid[p])
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(around((0, 0), &ORTHOGONAL), vec![2, 4]);
+        assert_eq!(around((1, 1), &ORTHOGONAL), vec![2, 6, 8, 4]);
+        assert_eq!(around((1, 1), &ADJACENT), vec![2, 3, 6, 9, 8, 7, 4, 1]);
+
+        let ray = |pos, step| grid.ray(pos, step).map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(ray((0, 0), Compass::SE.step()), vec![5, 9]);
+        assert_eq!(ray((1, 2), Direction::Up.step()), vec![5, 2]);
+        assert!(grid.ray((2, 1), Direction::Right.step()).next().is_none());
+    }
+
+    #[test]
+    fn views_columns() {
+        let grid = digits("12\n34\n56");
+
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 4, 6]);
+        assert_eq!(
+            grid.column(0).rev().copied().collect::<Vec<_>>(),
+            vec![5, 3, 1]
+        );
+        assert_eq!(grid.positions().nth(3), Some((1, 1)));
+    }
+}
@@ -0,0 +1,416 @@
+//! Exporting grids and point sets as images, for looking at the state behind
+//! an answer rather than reading it off the terminal.
+//!
+//! An [`Image`] is a grid of palette indices. Index 0 is the background and
+//! every other index picks a foreground colour from the [`Palette`]. Images
+//! are written as binary PPM or PGM rasters, or as SVG, with each cell drawn
+//! as a `scale` by `scale` square.
+//!
+//! ```
+//! use aoc_grid::Grid;
+//! use aoc_image::{Format, Image};
+//!
+//! let grid = Grid::parse("#.\n.#", "a map of pixels", "`#` or `.`", |c| Some(c == '#'))
+//!     .unwrap();
+//! let image = Image::from_grid(&grid, |on| *on as usize).with_scale(3);
+//! assert_eq!((image.width(), image.height()), (6, 6));
+//!
+//! let mut pgm = vec![];
+//! image.write(Format::Pgm, &mut pgm).unwrap();
+//! assert!(pgm.starts_with(b"P5\n6 6\n255\n"));
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc_geometry::Point;
+use aoc_grid::Grid;
+
+/// A colour, written as `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Perceived brightness, for greyscale output.
+    pub fn luma(self) -> u8 {
+        let Rgb(r, g, b) = self;
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected a colour like `#ff8800`, got `{}`", value);
+        let hex = value
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or_else(invalid)?;
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+
+        Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// Colours to draw palette indices with: a background colour followed by at
+/// least one foreground colour. Indices past the last colour wrap around the
+/// foreground colours, so any palette can draw any image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colours: Vec<Rgb>,
+}
+
+impl Palette {
+    /// Names of the built-in palettes, as accepted by [`Palette::from_str`].
+    pub const NAMES: [&'static str; 3] = ["mono", "crt", "rainbow"];
+
+    /// A palette of the background colour followed by the foreground colours.
+    /// Returns `None` if there are no foreground colours.
+    pub fn new(colours: Vec<Rgb>) -> Option<Self> {
+        (colours.len() >= 2).then_some(Self { colours })
+    }
+
+    /// Black on white.
+    pub fn mono() -> Self {
+        Self {
+            colours: vec![Rgb::WHITE, Rgb::BLACK],
+        }
+    }
+
+    /// Green on black, like the handheld's screen.
+    pub fn crt() -> Self {
+        Self {
+            colours: vec![Rgb(0x0c, 0x14, 0x0c), Rgb(0x33, 0xff, 0x66)],
+        }
+    }
+
+    /// Six distinct hues on a dark background, for images with many indices.
+    pub fn rainbow() -> Self {
+        Self {
+            colours: vec![
+                Rgb(0x20, 0x20, 0x20),
+                Rgb(0xe6, 0x19, 0x4b),
+                Rgb(0xf5, 0x82, 0x31),
+                Rgb(0xff, 0xe1, 0x19),
+                Rgb(0x3c, 0xb4, 0x4b),
+                Rgb(0x43, 0x63, 0xd8),
+                Rgb(0x91, 0x1e, 0xb4),
+            ],
+        }
+    }
+
+    /// The colour to draw `index` with.
+    pub fn colour(&self, index: usize) -> Rgb {
+        match index {
+            0 => self.colours[0],
+            _ => self.colours[1 + (index - 1) % (self.colours.len() - 1)],
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::mono()
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    /// Reads one of the [`Palette::NAMES`], or a comma separated list of
+    /// colours starting with the background, e.g. `#000000,#ffffff`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "mono" => return Ok(Self::mono()),
+            "crt" => return Ok(Self::crt()),
+            "rainbow" => return Ok(Self::rainbow()),
+            _ => (),
+        }
+
+        let colours = value
+            .split(',')
+            .map(|colour| colour.trim().parse())
+            .collect::<Result<Vec<Rgb>, _>>()?;
+
+        Self::new(colours).ok_or_else(|| {
+            format!(
+                "expected one of {} or at least two colours, got `{}`",
+                Self::NAMES.join(", "),
+                value
+            )
+        })
+    }
+}
+
+/// File formats an [`Image`] can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Binary colour raster (`P6`).
+    Ppm,
+    /// Binary greyscale raster (`P5`).
+    Pgm,
+    /// Scalable vector graphics, one rectangle per run of cells.
+    Svg,
+}
+
+impl Format {
+    /// The format matching the extension of `path`, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Format::Ppm),
+            "pgm" => Some(Format::Pgm),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// A grid of palette indices, ready to be written out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    cells: Grid<usize>,
+    palette: Palette,
+    scale: usize,
+}
+
+impl Image {
+    /// An image of `cells`, one pixel per cell, in the default palette.
+    pub fn new(cells: Grid<usize>) -> Self {
+        Self {
+            cells,
+            palette: Palette::default(),
+            scale: 1,
+        }
+    }
+
+    /// An image of `grid`, picking each cell's palette index with `index`.
+    pub fn from_grid<T>(grid: &Grid<T>, index: impl FnMut(&T) -> usize) -> Self {
+        Self::new(grid.map(index))
+    }
+
+    /// An image of the smallest rectangle holding all of `points`, with the
+    /// points drawn in index 1.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Self {
+        let points: Vec<Point> = points.into_iter().collect();
+        let (Some(min_x), Some(max_x)) = (
+            points.iter().map(|p| p.x).min(),
+            points.iter().map(|p| p.x).max(),
+        ) else {
+            return Self::new(Grid::new(0, 0, 0));
+        };
+        let min_y = points.iter().map(|p| p.y).min().unwrap();
+        let max_y = points.iter().map(|p| p.y).max().unwrap();
+
+        let origin = Point::new(min_x, min_y);
+        let size = Point::new(max_x, max_y) - origin + Point::new(1, 1);
+        let mut cells = Grid::new(size.x as usize, size.y as usize, 0);
+        for point in points {
+            let offset = point - origin;
+            cells[(offset.x as usize, offset.y as usize)] = 1;
+        }
+
+        Self::new(cells)
+    }
+
+    /// Draws the image with `palette`.
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Draws each cell as a `scale` by `scale` square of pixels.
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "images need a scale of at least 1");
+        self.scale = scale;
+        self
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> usize {
+        self.cells.width() * self.scale
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> usize {
+        self.cells.height() * self.scale
+    }
+
+    /// Writes the image to `out` in `format`.
+    pub fn write(&self, format: Format, out: &mut dyn Write) -> io::Result<()> {
+        match format {
+            Format::Ppm => self.write_raster(out, "P6", |rgb| vec![rgb.0, rgb.1, rgb.2]),
+            Format::Pgm => self.write_raster(out, "P5", |rgb| vec![rgb.luma()]),
+            Format::Svg => self.write_svg(out),
+        }
+    }
+
+    /// Writes the image to `path`, in the format its extension names.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let format = Format::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot tell the image format of {}; use .ppm, .pgm or .svg",
+                    path.display()
+                ),
+            )
+        })?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(format, &mut out)?;
+        out.flush()
+    }
+
+    /// Writes a binary Netpbm raster, encoding each colour with `pixel`.
+    fn write_raster(
+        &self,
+        out: &mut dyn Write,
+        magic: &str,
+        pixel: impl Fn(Rgb) -> Vec<u8>,
+    ) -> io::Result<()> {
+        write!(out, "{}\n{} {}\n255\n", magic, self.width(), self.height())?;
+
+        for row in self.cells.rows() {
+            let mut line = Vec::with_capacity(self.width() * 3);
+            for index in row {
+                let pixel = pixel(self.palette.colour(*index));
+                for _ in 0..self.scale {
+                    line.extend_from_slice(&pixel);
+                }
+            }
+
+            for _ in 0..self.scale {
+                out.write_all(&line)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes an SVG measured in cells and sized in pixels, merging runs of
+    /// equal cells along each row into a single rectangle.
+    fn write_svg(&self, out: &mut dyn Write) -> io::Result<()> {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            self.width(),
+            self.height(),
+            width,
+            height
+        )?;
+        writeln!(
+            out,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            self.palette.colour(0)
+        )?;
+
+        for (y, row) in self.cells.rows().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let run = row[x..].iter().take_while(|i| **i == row[x]).count();
+                if row[x] != 0 {
+                    writeln!(
+                        out,
+                        r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}"/>"#,
+                        x,
+                        y,
+                        run,
+                        self.palette.colour(row[x])
+                    )?;
+                }
+
+                x += run;
+            }
+        }
+
+        writeln!(out, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(image: &Image, format: Format) -> Vec<u8> {
+        let mut out = vec![];
+        image.write(format, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn parses_palettes() {
+        assert_eq!("crt".parse(), Ok(Palette::crt()));
+        assert_eq!(
+            "#000000, #FF8800".parse(),
+            Ok(Palette::new(vec![Rgb::BLACK, Rgb(255, 136, 0)]).unwrap())
+        );
+        assert!("#000000".parse::<Palette>().is_err());
+        assert!("#00000g,#ffffff".parse::<Palette>().is_err());
+        assert_eq!(Rgb(255, 136, 0).to_string(), "#ff8800");
+    }
+
+    #[test]
+    fn wraps_foreground_colours() {
+        let palette: Palette = "#000000,#ff0000,#00ff00".parse().unwrap();
+
+        assert_eq!(palette.colour(0), Rgb::BLACK);
+        assert_eq!(palette.colour(2), Rgb(0, 255, 0));
+        assert_eq!(palette.colour(3), Rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn writes_scaled_rasters() {
+        let cells = Grid::from_rows(vec![vec![1, 0]]).unwrap();
+        let image = Image::new(cells).with_scale(2);
+
+        let mut ppm = b"P6\n4 2\n255\n".to_vec();
+        for _ in 0..2 {
+            ppm.extend_from_slice(&[0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255]);
+        }
+        assert_eq!(bytes(&image, Format::Ppm), ppm);
+
+        let pgm = bytes(&image.with_palette(Palette::crt()), Format::Pgm);
+        assert_eq!(&pgm[11..], &[176, 176, 16, 16, 176, 176, 16, 16]);
+    }
+
+    #[test]
+    fn merges_svg_runs() {
+        let cells = Grid::from_rows(vec![vec![1, 1, 0, 2]]).unwrap();
+        let svg = String::from_utf8(bytes(&Image::new(cells).with_scale(10), Format::Svg)).unwrap();
+
+        assert!(svg.contains(r#"width="40" height="10" viewBox="0 0 4 1""#));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="2" height="1" fill="#000000"/>"##));
+        assert!(svg.contains(r##"<rect x="3" y="0" width="1" height="1" fill="#000000"/>"##));
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+
+    #[test]
+    fn frames_point_sets() {
+        let image = Image::from_points([Point::new(-1, 2), Point::new(1, 3)]);
+
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.cells.row(0), &[1, 0, 0]);
+        assert_eq!(image.cells.row(1), &[0, 0, 1]);
+        assert_eq!(Image::from_points([]).width(), 0);
+        assert_eq!(
+            Format::from_path(Path::new("out/rope.SVG")),
+            Some(Format::Svg)
+        );
+    }
+}
@@ -0,0 +1,505 @@
+//! Parsers shared by the days, built on [`nom`], that report what they
+//! expected and where.
+//!
+//! Parsers here fail with an [`Error`] holding the token they stopped at and
+//! a description of what they expected instead. [`parse_all`] runs a parser
+//! over a whole input and turns its failure into a [`ParseError`] pointing at
+//! that token's line and column. Wrapping a parser in
+//! [`nom::error::context`] describes what it expects; the innermost
+//! description wins, so the most specific one reaches the user. Between
+//! alternatives, the one that got furthest is reported.
+//!
+//! ```
+//! use aoc_parse::{comma_list, integer, key_value, parse_all};
+//! use nom::error::context;
+//!
+//! let items = || key_value("Starting items", comma_list(context("a worry level", integer::<i64>)));
+//! assert_eq!(parse_all("Starting items: 79, -98", items()), Ok(vec![79, -98]));
+//!
+//! let err = parse_all("Starting items: 79, x", items()).unwrap_err();
+//! assert_eq!((err.column, err.found.as_str()), (21, "x"));
+//! assert_eq!(err.expected, "a worry level");
+//! ```
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use aoc_core::ParseError;
+use aoc_grid::Grid;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, one_of, space0};
+use nom::combinator::{cut, opt, recognize};
+use nom::error::{ContextError, ErrorKind, FromExternalError};
+use nom::sequence::pair;
+use nom::Parser;
+
+/// Result of a parser in this crate.
+pub type IResult<'a, T> = nom::IResult<&'a str, T, Error<'a>>;
+
+/// Where a parser failed and what it expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<'a> {
+    /// The offending token, as a slice of the input; empty at the end of a
+    /// line or of the input.
+    pub found: &'a str,
+    /// Description of what was expected instead.
+    pub expected: Cow<'static, str>,
+    /// Whether `expected` was given by a context or the caller, rather than
+    /// made up from the parser that failed.
+    described: bool,
+}
+
+impl<'a> Error<'a> {
+    /// A failure at the next token of `input`, expecting `expected`.
+    pub fn at(input: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            found: token(input),
+            expected: expected.into(),
+            described: true,
+        }
+    }
+
+    /// A failure whose description gives way to any context around it.
+    fn fallback(input: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            described: false,
+            ..Self::at(input, expected)
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        let expected = match kind {
+            ErrorKind::Digit => "a digit",
+            ErrorKind::Alpha => "a letter",
+            ErrorKind::AlphaNumeric => "a letter or digit",
+            ErrorKind::Space | ErrorKind::MultiSpace => "a space",
+            ErrorKind::CrLf => "a line break",
+            ErrorKind::Eof => "the end of the input",
+            _ => "something else",
+        };
+
+        Self::fallback(input, expected)
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    /// Keeps whichever alternative got further, preferring a described one
+    /// when both stopped at the same place.
+    fn or(self, other: Self) -> Self {
+        let (here, there) = (self.found.as_ptr(), other.found.as_ptr());
+        if there > here || (there == here && (other.described || !self.described)) {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(_: &'a str, context: &'static str, other: Self) -> Self {
+        if other.described {
+            return other;
+        }
+
+        Self {
+            expected: context.into(),
+            described: true,
+            ..other
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        nom::error::ParseError::from_error_kind(input, kind)
+    }
+}
+
+/// The token at the start of `input`, after any spaces: a signed number, a
+/// word, or else a single character. Empty at the end of a line.
+fn token(input: &str) -> &str {
+    let input = input.trim_start_matches([' ', '\t']);
+    let sign = input
+        .strip_prefix(['-', '+'])
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+    let start = usize::from(sign);
+    let end = input[start..]
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(input.len(), |end| start + end);
+
+    if end > 0 {
+        &input[..end]
+    } else {
+        let c = input.chars().next().filter(|c| *c != '\n');
+        &input[..c.map_or(0, char::len_utf8)]
+    }
+}
+
+/// Fails at the next token of `input`, expecting `expected`. Unlike a
+/// context, the description is kept even if the parser is wrapped in one.
+pub fn fail<'a, T>(input: &'a str, expected: impl Into<Cow<'static, str>>) -> IResult<'a, T> {
+    Err(nom::Err::Error(Error::at(input, expected)))
+}
+
+/// Runs `parser` over the whole of `input`. A failure, or anything left
+/// over, is reported at the offending token.
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, T, Error<'a>>,
+) -> Result<T, ParseError> {
+    let err = match parser.parse(input) {
+        Ok(("", value)) => return Ok(value),
+        Ok((rest, _)) => Error::at(rest, "the end of the input"),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => err,
+        Err(nom::Err::Incomplete(_)) => Error::at(&input[input.len()..], "more input"),
+    };
+
+    Err(ParseError::at(input, err.found, err.expected))
+}
+
+/// Matches `text` exactly, expecting it quoted, e.g. ``expected `move` ``.
+pub fn literal<'a>(text: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input| {
+        tag(text)(input)
+            .map_err(|_: nom::Err<Error>| nom::Err::Error(Error::fallback(input, quoted(text))))
+    }
+}
+
+fn quoted(text: &str) -> String {
+    format!("`{}`", text.trim())
+}
+
+/// An integer with an optional sign, like `42`, `-7` or `+3`. It must not
+/// run into a letter or digit, so `12k` is rejected whole rather than read
+/// as `12`, and must fit in `T`.
+pub fn integer<'a, T: FromStr>(input: &'a str) -> IResult<'a, T> {
+    let number = recognize(pair(opt(one_of("+-")), digit1::<_, Error>))(input);
+    let fallback = || nom::Err::Error(Error::fallback(input, "a number"));
+
+    let (rest, text) = number.map_err(|_| fallback())?;
+    if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+        return Err(fallback());
+    }
+
+    text.parse().map(|n| (rest, n)).map_err(|_| fallback())
+}
+
+/// One or more characters in `class`, up to the end of the line. Any other
+/// character there is reported on its own, expecting `expected`, so `ab1B`
+/// read as letters fails at `1`.
+pub fn chars<'a>(
+    expected: &'static str,
+    class: impl Fn(char) -> bool,
+) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input| {
+        let end = input
+            .find(|c: char| c == '\n' || !class(c))
+            .unwrap_or(input.len());
+        let (text, rest) = input.split_at(end);
+
+        match rest.chars().next() {
+            Some(c) if c != '\n' => Err(nom::Err::Error(Error {
+                found: &rest[..c.len_utf8()],
+                expected: expected.into(),
+                described: true,
+            })),
+            _ if text.is_empty() => fail(rest, expected),
+            _ => Ok((rest, text)),
+        }
+    }
+}
+
+/// One or more `item`s separated by `separator`. Once a separator is read,
+/// an item must follow it.
+pub fn list<'a, S, T>(
+    mut separator: impl Parser<&'a str, S, Error<'a>>,
+    mut item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+
+        loop {
+            match separator.parse(input) {
+                Ok((rest, _)) => {
+                    let (rest, next) = cut(|i| item.parse(i))(rest)?;
+                    items.push(next);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => return Ok((input, items)),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Items separated by commas and optional spaces, like `79, 98`.
+pub fn comma_list<'a, T>(
+    item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    list(pair(char(','), space0), item)
+}
+
+/// A `key: value` line, possibly indented, like `  Test: divisible by 23`.
+pub fn key_value<'a, T>(
+    key: &'static str,
+    mut value: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    move |input| {
+        let (input, _) = space0(input)?;
+        let (input, _) = pair(tag(key), char(':'))(input).map_err(|_: nom::Err<Error>| {
+            nom::Err::Error(Error::fallback(input, quoted(&format!("{}:", key))))
+        })?;
+        let (input, _) = space0(input)?;
+
+        value.parse(input)
+    }
+}
+
+/// Succeeds, consuming nothing, at the end of a line or of the input.
+fn end_of_line(input: &str) -> IResult<'_, ()> {
+    if input.is_empty() || input.starts_with('\n') {
+        Ok((input, ()))
+    } else {
+        Err(nom::Err::Error(Error::fallback(
+            input,
+            "the end of the line",
+        )))
+    }
+}
+
+/// A line break that doesn't start a blank line.
+fn line_break(input: &str) -> IResult<'_, char> {
+    let (rest, c) = char('\n')(input)?;
+    if rest.starts_with('\n') {
+        return Err(nom::Err::Error(Error::fallback(input, "a line")));
+    }
+
+    Ok((rest, c))
+}
+
+/// One `item` per line, each taking up its whole line, up to a blank line or
+/// the end of the input.
+pub fn lines<'a, T>(
+    mut item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    list(line_break, move |input| {
+        let (rest, value) = item.parse(input)?;
+        end_of_line(rest)?;
+        Ok((rest, value))
+    })
+}
+
+/// Blocks of lines separated by a blank line, each read by `block`, which
+/// must take up every line of its block.
+pub fn blocks<'a, T>(
+    mut block: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    list(tag("\n\n"), move |input| {
+        let (rest, value) = block.parse(input)?;
+        match rest.strip_prefix('\n') {
+            Some(next) if !next.starts_with('\n') => fail(next, "a blank line"),
+            _ => end_of_line(rest).map(|_| (rest, value)),
+        }
+    })
+}
+
+/// A character map, one row per line up to a blank line or the end of the
+/// input, read as [`Grid::parse`] does. `map` names the whole map, as a plural
+/// noun phrase, and `expected` a single cell.
+pub fn grid<'a, T>(
+    map: &'static str,
+    expected: &'static str,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Grid<T>> {
+    move |input| {
+        let end = input.find("\n\n").unwrap_or(input.len());
+        let (text, rest) = input.split_at(end);
+
+        match Grid::parse(text, map, expected, &mut cell) {
+            Ok(grid) => Ok((rest, grid)),
+            Err(err) => {
+                let found = locate(text, &err);
+                Err(nom::Err::Error(Error {
+                    found,
+                    expected: err.expected.into(),
+                    described: true,
+                }))
+            }
+        }
+    }
+}
+
+/// The slice of `text` that `err`, reported against `text`, found.
+fn locate<'a>(text: &'a str, err: &ParseError) -> &'a str {
+    let line_start: usize = text
+        .split('\n')
+        .take(err.line - 1)
+        .map(|line| line.len() + 1)
+        .sum();
+    let line = &text[line_start.min(text.len())..];
+    let start = line
+        .char_indices()
+        .nth(err.column - 1)
+        .map_or(line.len(), |(i, _)| i);
+
+    let rest = &line[start..];
+    &rest[..err.found.len().min(rest.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::error::context;
+    use nom::sequence::{preceded, separated_pair};
+
+    fn position(err: ParseError) -> (usize, usize, String) {
+        (err.line, err.column, err.found)
+    }
+
+    #[test]
+    fn reads_integers() {
+        assert_eq!(parse_all("-12", integer::<i32>), Ok(-12));
+        assert_eq!(parse_all("+3", integer::<u8>), Ok(3));
+
+        let err = parse_all("12k", integer::<u32>).unwrap_err();
+        assert_eq!(
+            (err.found.as_str(), err.expected.as_str()),
+            ("12k", "a number")
+        );
+        assert_eq!(
+            position(parse_all("-1", integer::<u32>).unwrap_err()).2,
+            "-1"
+        );
+        assert_eq!(
+            position(parse_all("256", integer::<u8>).unwrap_err()).2,
+            "256"
+        );
+    }
+
+    #[test]
+    fn reports_the_innermost_context() {
+        let range = || {
+            context(
+                "a range like `2-4`",
+                separated_pair(
+                    context("a section ID", integer::<u32>),
+                    char('-'),
+                    context("a section ID", integer::<u32>),
+                ),
+            )
+        };
+
+        let err = parse_all("2-x", range()).unwrap_err();
+        assert_eq!(err.expected, "a section ID");
+        assert_eq!(position(err), (1, 3, "x".into()));
+
+        let err = parse_all("2_4", range()).unwrap_err();
+        assert_eq!(err.expected, "a section ID");
+
+        let err = parse_all("2:4", range()).unwrap_err();
+        assert_eq!(err.expected, "a range like `2-4`");
+        assert_eq!(position(err), (1, 2, ":".into()));
+    }
+
+    #[test]
+    fn reports_the_furthest_alternative() {
+        let command = || {
+            context(
+                "`addx V` or `noop`",
+                nom::branch::alt((
+                    preceded(literal("addx "), context("a value", integer::<i32>)),
+                    nom::combinator::value(0, literal("noop")),
+                )),
+            )
+        };
+
+        let err = parse_all("addx x", command()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (6, "a value"));
+
+        let err = parse_all("mulx 2", command()).unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (1, "`addx V` or `noop`")
+        );
+        assert_eq!(err.found, "mulx");
+    }
+
+    #[test]
+    fn reads_character_runs() {
+        let letters = || lines(chars("a letter", |c| c.is_ascii_alphabetic()));
+        assert_eq!(parse_all("abAB\nc", letters()), Ok(vec!["abAB", "c"]));
+
+        let err = parse_all("abAB\nab1B", letters()).unwrap_err();
+        assert_eq!(position(err), (2, 3, "1".into()));
+
+        let err = parse_all("", letters()).unwrap_err();
+        assert_eq!(
+            (err.found.as_str(), err.expected.as_str()),
+            ("", "a letter")
+        );
+    }
+
+    #[test]
+    fn reads_lines_and_blocks() {
+        let elves = || blocks(lines(integer::<u32>));
+        assert_eq!(
+            parse_all("1\n2\n\n3", elves()),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+
+        let err = parse_all("1\n2\n\n3 4", elves()).unwrap_err();
+        assert_eq!(position(err), (4, 3, "4".into()));
+
+        let err = parse_all("1\n\n\n3", elves()).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let pairs = blocks(|input| {
+            let (input, a) = integer::<u32>(input)?;
+            let (input, b) = preceded(char('\n'), integer::<u32>)(input)?;
+            Ok((input, (a, b)))
+        });
+        let err = parse_all("1\n2\n3", pairs).unwrap_err();
+        assert_eq!(err.expected, "a blank line");
+        assert_eq!(position(err), (3, 1, "3".into()));
+    }
+
+    #[test]
+    fn reads_comma_lists_and_keys() {
+        let items = || key_value("Starting items", opt(comma_list(integer::<i64>)));
+
+        assert_eq!(
+            parse_all("  Starting items: 1,2, 3", items()),
+            Ok(Some(vec![1, 2, 3]))
+        );
+        assert_eq!(parse_all("  Starting items:", items()), Ok(None));
+
+        let err = parse_all("Starting items: 1, ", items()).unwrap_err();
+        assert_eq!(position(err), (1, 20, "".into()));
+
+        let err = parse_all("Starting: 1", items()).unwrap_err();
+        assert_eq!(err.expected, "`Starting items:`");
+        assert_eq!(err.found, "Starting");
+    }
+
+    #[test]
+    fn reads_grids() {
+        let digits = || grid("a map of digits", "a digit", |c| c.to_digit(10));
+
+        let grid = parse_all("12\n34", digits()).unwrap();
+        assert_eq!(grid.row(1), &[3, 4]);
+
+        let err = parse_all("12\n3x", digits()).unwrap_err();
+        assert_eq!(position(err), (2, 2, "x".into()));
+
+        let err = parse_all("12\n345", digits()).unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a row 2 cells wide"));
+
+        let (rest, _) = digits()("12\n34\n\nrest").unwrap();
+        assert_eq!(rest, "\n\nrest");
+    }
+}
@@ -0,0 +1,145 @@
+//! Day 1: Calorie Counting.
+//!
+//! ```
+//! use aoc_01::Day01;
+//! use aoc_core::Solution;
+//!
+//! let elves = Day01::parse("1000\n2000\n\n4000\n\n5000\n6000").unwrap();
+//! assert_eq!(elves.totals(), [11000, 4000, 3000]);
+//! assert_eq!(Day01::part1(&elves), 11000);
+//! ```
+
+use aoc_core::{Lines, ParseError, Solution, StreamError, Streaming};
+use aoc_parse::{integer, parse_all};
+use nom::error::context;
+
+/// The largest calorie totals carried by any elf, most first. Only the top
+/// three are kept, as that is all either part needs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TopThree([u32; 3]);
+
+impl TopThree {
+    /// Records an elf's total, keeping it if it is among the top three.
+    pub fn push(&mut self, total: u32) {
+        if let Some(i) = self.0.iter().position(|top| total > *top) {
+            self.0[i..].rotate_right(1);
+            self.0[i] = total;
+        }
+    }
+
+    /// The top three totals, most first.
+    pub fn totals(&self) -> [u32; 3] {
+        self.0
+    }
+}
+
+/// Adds up each elf's calories as the lines arrive.
+#[derive(Debug, Default)]
+struct Tally {
+    elf: u32,
+    top: TopThree,
+}
+
+impl Tally {
+    /// Counts a line: a calorie count, or the blank line after an elf.
+    fn line(&mut self, line: &str) -> Result<(), ParseError> {
+        if line.is_empty() {
+            self.top.push(std::mem::take(&mut self.elf));
+            return Ok(());
+        }
+
+        self.elf += parse_all(line, context("a calorie count", integer::<u32>))?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> TopThree {
+        // The last elf isn't followed by a blank line.
+        self.top.push(self.elf);
+        self.top
+    }
+}
+
+/// Finds the elves carrying the most calories.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    /// Calories carried by the top three elves.
+    type Input = TopThree;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut tally = Tally::default();
+        for line in input.lines() {
+            tally.line(line).map_err(|err| err.within(input, line))?;
+        }
+
+        Ok(tally.finish())
+    }
+
+    /// Finds the most calories carried by a single elf.
+    fn part1(top: &Self::Input) -> Self::Part1 {
+        top.totals()[0]
+    }
+
+    /// Sums the calories carried by the top three elves.
+    fn part2(top: &Self::Input) -> Self::Part2 {
+        top.totals().iter().sum()
+    }
+}
+
+impl Streaming for Day01 {
+    fn stream(lines: Lines<'_>) -> Result<(Self::Part1, Self::Part2), StreamError> {
+        let mut tally = Tally::default();
+        for line in lines {
+            let line = line?;
+            tally
+                .line(&line.text)
+                .map_err(|err| err.on_line(line.number))?;
+        }
+
+        let top = tally.finish();
+        Ok((Self::part1(&top), Self::part2(&top)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_top_three() {
+        let mut top = TopThree::default();
+        for total in [5, 1, 9, 5, 7] {
+            top.push(total);
+        }
+
+        assert_eq!(top.totals(), [9, 7, 5]);
+    }
+
+    #[test]
+    fn streams_input() {
+        let input = "1000\r\n2000\r\n\r\n4000\r\n\r\n20O0\r\n";
+        let err = aoc_core::solve_stream::<Day01>(&mut input.as_bytes()).unwrap_err();
+
+        match err {
+            StreamError::Parse(err) => assert_eq!((err.line, err.column), (6, 1)),
+            err => panic!("unexpected error: {}", err),
+        }
+        assert_eq!(
+            aoc_core::solve_stream::<Day01>(&mut "1\n\n3\n4\n\n2\n".as_bytes()).unwrap(),
+            aoc_core::solve::<Day01>("1\n\n3\n4\n\n2").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_non_numeric_calories() {
+        let err = Day01::parse("1000\n\n20O0").unwrap_err();
+
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found, "20O0");
+    }
+}
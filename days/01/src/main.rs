@@ -1,32 +1,10 @@
-use std::io::prelude::*;
-use std::io::stdin;
+use aoc_core::Solution;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input)?;
-    let mut payloads = vec![];
-    let mut elf: Vec<&str> = vec![];
+    aoc_core::log::init(None)?;
+    let mut input = aoc_core::input::open(aoc_01::Day01::DAY)?;
 
-    for line in input.lines() {
-        if line.is_empty() {
-            let total: u32 = elf.iter().map(|t| t.parse::<u32>().unwrap()).sum();
-            payloads.push(total);
-            elf.clear();
-
-            continue;
-        }
-
-        elf.push(line);
-    }
-
-    // Handle last entry without trailing empty line.
-    let total: u32 = elf.iter().map(|t| t.parse::<u32>().unwrap()).sum();
-    payloads.push(total);
-
-    println!("Max - {}", payloads.iter().max().unwrap());
-    payloads.sort();
-    payloads.reverse();
-    println!("Top 3 - {}", payloads.iter().take(3).sum::<u32>());
+    print!("{}", aoc_core::solve_stream::<aoc_01::Day01>(&mut input)?);
 
     Ok(())
 }
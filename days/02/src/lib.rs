@@ -0,0 +1,249 @@
+//! Day 2: Rock Paper Scissors.
+//!
+//! ```
+//! use aoc_02::{parse, Shape, Strategy};
+//!
+//! let guide = parse("A Y\nB X\nC Z").unwrap();
+//! assert_eq!(guide[0].expected, Shape::Rock);
+//! assert_eq!(guide[0].strategy, Strategy::Y);
+//! ```
+
+use aoc_core::{ParseError, Solution};
+use aoc_parse::{lines, parse_all, IResult};
+use nom::character::complete::{alpha1, char};
+use nom::combinator::map_opt;
+use nom::error::context;
+use nom::sequence::{pair, preceded};
+
+/// The result of a round from a player's point of view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+impl Outcome {
+    /// Reads the second column as the outcome the round needs to end in.
+    pub fn from_strategy(v: Strategy) -> Self {
+        match v {
+            Strategy::X => Outcome::Lose,
+            Strategy::Y => Outcome::Draw,
+            Strategy::Z => Outcome::Win,
+        }
+    }
+
+    /// Points awarded for the outcome of a round.
+    pub fn score(&self) -> u32 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+        }
+    }
+}
+
+/// The second column of the strategy guide, read as a move to play in part 1
+/// and as the outcome to aim for in part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    X,
+    Y,
+    Z,
+}
+
+impl Strategy {
+    fn from_column(v: &str) -> Option<Self> {
+        match v {
+            "X" => Some(Strategy::X),
+            "Y" => Some(Strategy::Y),
+            "Z" => Some(Strategy::Z),
+            _ => None,
+        }
+    }
+}
+
+/// A hand shape played in a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissor,
+}
+
+impl Shape {
+    fn from_expected(v: &str) -> Option<Self> {
+        match v {
+            "A" => Some(Shape::Rock),
+            "B" => Some(Shape::Paper),
+            "C" => Some(Shape::Scissor),
+            _ => None,
+        }
+    }
+
+    /// Reads the second column as the shape to play.
+    pub fn from_strategy(v: Strategy) -> Self {
+        match v {
+            Strategy::X => Shape::Rock,
+            Strategy::Y => Shape::Paper,
+            Strategy::Z => Shape::Scissor,
+        }
+    }
+
+    /// Points awarded for playing the shape.
+    pub fn selection_score(&self) -> u32 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissor => 3,
+        }
+    }
+
+    /// The shape to play against `self` to reach the given outcome.
+    pub fn outcome_needs(&self, result: &Outcome) -> Shape {
+        if result == &Outcome::Win {
+            return match self {
+                Shape::Rock => Shape::Paper,
+                Shape::Paper => Shape::Scissor,
+                Shape::Scissor => Shape::Rock,
+            };
+        }
+
+        if result == &Outcome::Lose {
+            return match self {
+                Shape::Rock => Shape::Scissor,
+                Shape::Paper => Shape::Rock,
+                Shape::Scissor => Shape::Paper,
+            };
+        }
+
+        self.clone()
+    }
+
+    /// The outcome of playing `self` against `opponent`.
+    pub fn beats(&self, opponent: &Shape) -> Outcome {
+        if self == &Shape::Rock {
+            return match opponent {
+                Shape::Rock => Outcome::Draw,
+                Shape::Paper => Outcome::Lose,
+                Shape::Scissor => Outcome::Win,
+            };
+        }
+
+        if self == &Shape::Paper {
+            return match opponent {
+                Shape::Rock => Outcome::Win,
+                Shape::Paper => Outcome::Draw,
+                Shape::Scissor => Outcome::Lose,
+            };
+        }
+
+        match opponent {
+            Shape::Rock => Outcome::Lose,
+            Shape::Paper => Outcome::Win,
+            Shape::Scissor => Outcome::Draw,
+        }
+    }
+}
+
+/// A round played between the opponent (player 1) and us (player 2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub player_1: Shape,
+    pub player_2: Shape,
+}
+
+impl Round {
+    /// Our score for the round: the shape we played plus the outcome.
+    pub fn p2_score(&self) -> u32 {
+        self.player_2.beats(&self.player_1).score() + self.player_2.selection_score()
+    }
+}
+
+/// A line of the strategy guide: the opponent's move and the suggested response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    pub expected: Shape,
+    pub strategy: Strategy,
+}
+
+/// Parses a line of the guide, like `A Y`.
+fn guide(input: &str) -> IResult<'_, Guide> {
+    let (input, (expected, strategy)) = pair(
+        context("`A`, `B` or `C`", map_opt(alpha1, Shape::from_expected)),
+        context(
+            "`X`, `Y` or `Z`",
+            preceded(char(' '), map_opt(alpha1, Strategy::from_column)),
+        ),
+    )(input)?;
+
+    Ok((input, Guide { expected, strategy }))
+}
+
+/// Parses the strategy guide, one line per round.
+pub fn parse(strategy: &str) -> Result<Vec<Guide>, ParseError> {
+    parse_all(strategy, lines(guide))
+}
+
+/// Plays out the guide. When `rigged`, the second column is the outcome the
+/// round needs rather than the shape to play.
+pub fn rounds(guide: &[Guide], rigged: bool) -> Vec<Round> {
+    guide
+        .iter()
+        .map(|line| {
+            let player_1 = line.expected.clone();
+            let player_2 = if rigged {
+                player_1.outcome_needs(&Outcome::from_strategy(line.strategy))
+            } else {
+                Shape::from_strategy(line.strategy)
+            };
+
+            Round { player_1, player_2 }
+        })
+        .collect()
+}
+
+/// Sums our score over every round.
+pub fn total_score(rounds: &[Round]) -> u32 {
+    rounds.iter().map(|round| round.p2_score()).sum()
+}
+
+/// Scores the rock paper scissors tournament.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Input = Vec<Guide>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    /// Treats the second column as the shape to play.
+    fn part1(guide: &Self::Input) -> Self::Part1 {
+        total_score(&rounds(guide, false))
+    }
+
+    /// Treats the second column as the outcome the round needs.
+    fn part2(guide: &Self::Input) -> Self::Part2 {
+        total_score(&rounds(guide, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        let err = parse("A Y\nD X").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 1, "D"));
+
+        let err = parse("A Y\nB").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, ""));
+    }
+}
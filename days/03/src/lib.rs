@@ -0,0 +1,224 @@
+//! Day 3: Rucksack Reorganization.
+//!
+//! ```
+//! assert_eq!(aoc_03::priority('p'), 16);
+//! assert_eq!(aoc_03::misplaced_priority("vJrwpWtwJgWrhcsFMMfFFhFp"), 16);
+//! ```
+
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+
+use aoc_core::{Lines, ParseError, Solution, StreamError, Streaming};
+use aoc_parse::{chars, fail, lines, parse_all, IResult};
+use serde::Deserialize;
+
+/// Puzzle constants, read from the `[day03]` section of the workspace
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Number of elves in a group sharing a badge.
+    pub group_size: NonZeroUsize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            group_size: NonZeroUsize::new(3).unwrap(),
+        }
+    }
+}
+
+impl aoc_core::config::Section for Config {}
+
+impl Config {
+    /// The installed configuration, or the puzzle's constants if there is
+    /// none.
+    pub fn load() -> Self {
+        aoc_core::config::get(Day03::DAY)
+    }
+}
+
+/// Priority of an item type: `a` through `z` are 1 through 26 and `A` through
+/// `Z` are 27 through 52.
+pub fn priority(c: char) -> u32 {
+    if c.is_lowercase() {
+        return c as u32 - 96;
+    }
+
+    c as u32 - 64 + 26
+}
+
+/// Sums the priority of the items found in both compartments of a rucksack.
+pub fn misplaced_priority(rucksack: &str) -> u32 {
+    let front = &rucksack[0..rucksack.len() / 2];
+    let back = &rucksack[rucksack.len() / 2..];
+
+    let front = front
+        .chars()
+        .filter(|c| back.contains(*c))
+        .collect::<HashSet<char>>();
+
+    front.iter().map(|c| priority(*c)).sum::<u32>()
+}
+
+/// Finds the priority of the badge carried by every elf in the group: the
+/// lowest if they share several item types, or `None` if they share none.
+pub fn badge_priority(group: &[impl AsRef<str>]) -> Option<u32> {
+    group
+        .iter()
+        .map(|bag| bag.as_ref().chars().collect::<HashSet<char>>())
+        .reduce(|shared, bag| &shared & &bag)?
+        .into_iter()
+        .map(priority)
+        .min()
+}
+
+/// The group's badge priority, or an error at its last rucksack if the group
+/// has no badge.
+fn badge(group: &[impl AsRef<str>]) -> Result<u32, ParseError> {
+    let last = group.last().map_or("", |bag| bag.as_ref());
+
+    badge_priority(group).ok_or_else(|| {
+        ParseError::at(
+            last,
+            last,
+            "a rucksack sharing an item type with the rest of its group",
+        )
+    })
+}
+
+/// The items of a rucksack, split evenly between its two compartments.
+fn rucksack(input: &str) -> IResult<'_, &str> {
+    let (rest, items) = chars("an item type from `a` to `z` or `A` to `Z`", |c| {
+        c.is_ascii_alphabetic()
+    })(input)?;
+
+    if !items.len().is_multiple_of(2) {
+        return fail(input, "the same number of items in both compartments");
+    }
+
+    Ok((rest, items))
+}
+
+/// The elves' rucksacks, with each group's badge found while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksacks {
+    /// Items packed in each rucksack.
+    pub items: Vec<String>,
+    /// Priority of each group's badge.
+    pub badges: Vec<u32>,
+}
+
+/// Finds the misplaced items and badges in the elves' rucksacks.
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    /// Items packed in each rucksack, and the groups' badges.
+    type Input = Rucksacks;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let rucksacks = parse_all(input, lines(rucksack))?;
+        let badges = rucksacks
+            .chunks(Config::load().group_size.get())
+            .map(|group| {
+                let last = group.last().copied().unwrap_or_default();
+                badge(group).map_err(|err| err.within(input, last))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Rucksacks {
+            items: rucksacks.into_iter().map(String::from).collect(),
+            badges,
+        })
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Self::Part1 {
+        rucksacks
+            .items
+            .iter()
+            .map(|bag| misplaced_priority(bag))
+            .sum()
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Self::Part2 {
+        rucksacks.badges.iter().sum()
+    }
+}
+
+impl Streaming for Day03 {
+    /// Keeps only the current group of rucksacks.
+    fn stream(lines: Lines<'_>) -> Result<(Self::Part1, Self::Part2), StreamError> {
+        let size = Config::load().group_size.get();
+        let (mut misplaced, mut badges) = (0, 0);
+        let mut group = Vec::with_capacity(size);
+        let mut number = 0;
+
+        for line in lines {
+            let line = line?;
+            number = line.number;
+            parse_all(&line.text, rucksack).map_err(|err| err.on_line(number))?;
+
+            misplaced += misplaced_priority(&line.text);
+            group.push(line.text);
+            if group.len() == size {
+                badges += badge(&group).map_err(|err| err.on_line(number))?;
+                group.clear();
+            }
+        }
+
+        if !group.is_empty() {
+            badges += badge(&group).map_err(|err| err.on_line(number))?;
+        }
+
+        Ok((misplaced, badges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_input() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\r\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\r\nPmmdzqPrVvPwwTWBwg\r\n";
+
+        assert_eq!(
+            aoc_core::solve_stream::<Day03>(&mut input.as_bytes()).unwrap(),
+            aoc_core::solve::<Day03>(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_items() {
+        let err = Day03::parse("abAB\nab1B").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "1"));
+
+        let err = Day03::parse("abA").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn finds_lowest_shared_badge() {
+        assert_eq!(badge_priority(&["aBcB", "Bcxy"]), Some(3));
+        assert_eq!(badge_priority(&["vJrwpWtwJgWrhcsFMMfFFhFp"]), Some(3));
+        assert_eq!(badge_priority(&["abab", "cdcd"]), None);
+    }
+
+    #[test]
+    fn rejects_groups_without_badge() {
+        let input = "abab\nacac\nadad\nxyxy\nxzxz\nwwww";
+        assert_eq!(Day03::parse(&input[..14]).unwrap().badges, vec![1]);
+
+        let err = Day03::parse(input).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (6, 1, "wwww"));
+
+        let err = aoc_core::solve_stream::<Day03>(&mut input.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 6"), "{}", err);
+    }
+}
@@ -0,0 +1,173 @@
+//! Day 4: Camp Cleanup.
+//!
+//! ```
+//! use aoc_04::{parse, Assignment};
+//!
+//! assert_eq!(parse("2-4,6-8"), Ok(vec![Assignment(2, 4), Assignment(6, 8)]));
+//! ```
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use aoc_core::{ParseError, Solution};
+use aoc_parse::{integer, lines, parse_all, IResult};
+use nom::character::complete::char;
+use nom::error::context;
+use nom::sequence::separated_pair;
+
+/// An inclusive range of section IDs assigned to an elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment(pub u32, pub u32);
+
+impl Assignment {
+    /// Every section ID covered by the assignment.
+    pub fn sections(&self) -> HashSet<u32> {
+        (self.0..=self.1).collect()
+    }
+}
+
+impl fmt::Display for Assignment {
+    /// Writes the range as in the puzzle, e.g. `2-4`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.0, self.1)
+    }
+}
+
+/// Parses a range of sections, like `2-4`.
+fn assignment(input: &str) -> IResult<'_, Assignment> {
+    let section = || context("a section ID", integer::<u32>);
+    let (input, (start, end)) = context(
+        "a range like `2-4`",
+        separated_pair(section(), char('-'), section()),
+    )(input)?;
+
+    Ok((input, Assignment(start, end)))
+}
+
+impl TryFrom<&str> for Assignment {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Assignment, ParseError> {
+        parse_all(value, assignment)
+    }
+}
+
+/// Parses the assignment pairs, flattening each pair into two consecutive
+/// assignments.
+pub fn parse(input: &str) -> Result<Vec<Assignment>, ParseError> {
+    let pair = context(
+        "a pair of ranges like `2-4,6-8`",
+        separated_pair(assignment, char(','), assignment),
+    );
+    let pairs = parse_all(input, lines(pair))?;
+
+    Ok(pairs.into_iter().flat_map(|(a, b)| [a, b]).collect())
+}
+
+/// Writes the assignments back out as [`parse`] reads them, one pair per line.
+pub fn render(assignments: &[Assignment]) -> String {
+    assignments
+        .chunks(2)
+        .map(|pair| {
+            pair.iter()
+                .map(Assignment::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Finds overlapping cleanup assignments.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    /// Section assignments, with each pair of elves listed consecutively.
+    type Input = Vec<Assignment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    /// Counts pairs where one assignment fully contains the other.
+    fn part1(assignments: &Self::Input) -> Self::Part1 {
+        let mut count: usize = 0;
+        for pair in assignments.chunks(2) {
+            let first = &pair[0].sections();
+            let second = &pair[1].sections();
+
+            if first.is_superset(second) || second.is_superset(first) {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Counts pairs whose assignments overlap at all.
+    fn part2(assignments: &Self::Input) -> Self::Part2 {
+        let mut any_overlap: usize = 0;
+        for pair in assignments.chunks(2) {
+            let first = &pair[0].sections();
+            let second = &pair[1].sections();
+
+            if !first.is_disjoint(second) {
+                any_overlap += 1;
+            }
+        }
+
+        any_overlap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn assignment() -> impl Strategy<Value = Assignment> {
+        (any::<u32>(), any::<u32>()).prop_map(|(start, end)| Assignment(start, end))
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_assignments(assignment in assignment()) {
+            let text = assignment.to_string();
+            prop_assert_eq!(Assignment::try_from(text.as_str()), Ok(assignment));
+        }
+
+        #[test]
+        fn round_trips_pairs(pairs in prop::collection::vec((assignment(), assignment()), 1..50)) {
+            let assignments: Vec<Assignment> = pairs.into_iter().flat_map(|(a, b)| [a, b]).collect();
+            prop_assert_eq!(parse(&render(&assignments)), Ok(assignments));
+        }
+    }
+
+    #[test]
+    fn assignment_section_hashset() {
+        let mut expected = std::collections::HashSet::new();
+        for i in 0..=5 {
+            expected.insert(i);
+        }
+
+        assert_eq!(Assignment(0, 5).sections(), expected);
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        let err = parse("2-4,6-8\n2-3,4_5").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 5, "4_5"));
+
+        let err = parse("2-4,6-x").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 7, "x"));
+
+        let err = parse("2-4").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 4, ""));
+    }
+}
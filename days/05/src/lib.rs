@@ -0,0 +1,393 @@
+//! Day 5: Supply Stacks.
+//!
+//! ```
+//! use aoc_05::{parse_commands, parse_stacks};
+//!
+//! let mut layout = parse_stacks("[A]    \n[B] [C]\n 1   2 ").unwrap();
+//! for cmd in parse_commands("move 1 from 1 to 2").unwrap() {
+//!     layout.cmd_move(cmd);
+//! }
+//! assert_eq!(layout.tops(), "BA");
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use aoc_core::{ParseError, Solution};
+use aoc_parse::{integer, lines, list, literal, parse_all, IResult};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{anychar, char, space0, space1};
+use nom::combinator::{map, value, verify};
+use nom::error::context;
+use nom::sequence::{delimited, preceded, tuple};
+
+/// Parses a single crate slot: `[X]` for a crate or three spaces for none.
+pub fn container(input: &str) -> IResult<'_, Option<char>> {
+    context(
+        "a crate like `[A]` or an empty slot",
+        alt((
+            map(delimited(char('['), anychar, char(']')), Some),
+            value(None, tag("   ")),
+        )),
+    )(input)
+}
+
+/// Parses a row of the stack drawing into the crate in each stack, if any.
+pub fn stack_row(input: &str) -> IResult<'_, Vec<Option<char>>> {
+    list(char(' '), container)(input)
+}
+
+/// Parses the stack numbers at the foot of the drawing, like ` 1   2`.
+fn stack_numbers(input: &str) -> IResult<'_, Vec<usize>> {
+    preceded(space0, list(space1, context("a stack number", integer)))(input)
+}
+
+/// Parses a rearrangement step, like `move 1 from 2 to 1`.
+fn command(input: &str) -> IResult<'_, Command> {
+    let stack = || {
+        context(
+            "a stack number starting from 1",
+            map(verify(integer::<usize>, |n| *n > 0), |n| n - 1),
+        )
+    };
+
+    let (input, (count, from, to)) = context(
+        "a command like `move 1 from 2 to 1`",
+        tuple((
+            preceded(literal("move "), context("a crate count", integer)),
+            preceded(literal(" from "), stack()),
+            preceded(literal(" to "), stack()),
+        )),
+    )(input)?;
+
+    Ok((input, Command { from, to, count }))
+}
+
+/// Crates in a single stack, from bottom to top.
+pub type Stack = Vec<char>;
+
+/// The stacks of crates in the cargo hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    stacks: Vec<Stack>,
+}
+
+impl Layout {
+    /// Creates `num` empty stacks.
+    pub fn new(num: usize) -> Self {
+        let mut stacks = vec![];
+
+        for _ in 0..num {
+            stacks.push(vec![]);
+        }
+
+        Self { stacks }
+    }
+
+    /// Places a crate on top of the stack at index `to`.
+    pub fn add(&mut self, to: usize, value: char) {
+        self.stacks[to].push(value);
+    }
+
+    /// Runs the command with a crane that moves one crate at a time.
+    pub fn cmd_move(&mut self, cmd: Command) {
+        for _ in 0..cmd.count {
+            let swap = self.stacks[cmd.from].pop();
+
+            if let Some(swap) = swap {
+                self.stacks[cmd.to].push(swap);
+            }
+        }
+    }
+
+    /// Runs the command with a crane that moves all the crates at once,
+    /// keeping their order.
+    pub fn cmd_bulk_move(&mut self, cmd: Command) {
+        let available_count = self.stacks[cmd.from].len();
+        let qty = if available_count < cmd.count {
+            available_count
+        } else {
+            cmd.count
+        };
+
+        let stack = self.stacks[cmd.from].split_off(available_count - qty);
+        self.stacks[cmd.to].extend_from_slice(&stack);
+    }
+
+    /// The stacks, in order.
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    /// The crate on top of each non-empty stack.
+    pub fn tops(&self) -> String {
+        let mut tops = String::new();
+
+        for stack in &self.stacks {
+            if let Some(c) = stack.last() {
+                tops.push(*c);
+            }
+        }
+
+        tops
+    }
+}
+
+impl From<Vec<Stack>> for Layout {
+    fn from(stacks: Vec<Stack>) -> Self {
+        Self { stacks }
+    }
+}
+
+impl fmt::Display for Layout {
+    /// Draws the stacks as in the puzzle, with the stack numbers at the foot.
+    /// Only single digit stack numbers line up with the crates above them.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        let numbers: Vec<String> = (1..=self.stacks.len())
+            .map(|n| format!(" {} ", n))
+            .collect();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+/// A step of the rearrangement procedure. Stack indices are zero based.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Command {
+    pub from: usize,
+    pub to: usize,
+    pub count: usize,
+}
+
+impl TryFrom<&str> for Command {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Command, ParseError> {
+        parse_all(value, command)
+    }
+}
+
+impl fmt::Display for Command {
+    /// Writes the command as in the puzzle, with stacks numbered from 1.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/// Parses the stack drawing, including the line of stack numbers at its foot.
+pub fn parse_stacks(input: &str) -> Result<Layout, ParseError> {
+    let (rows, numbers) = input.rsplit_once('\n').unwrap_or(("", input));
+    let numbers = numbers.trim_end();
+    let numbers = parse_all(numbers, stack_numbers).map_err(|err| err.within(input, numbers))?;
+    let mut stacks = Layout::new(numbers.len());
+
+    for line in rows.lines().rev() {
+        let row = parse_all(line, stack_row).map_err(|err| err.within(input, line))?;
+
+        for (i, v) in row.iter().enumerate() {
+            if let Some(c) = v {
+                if i >= stacks.stacks.len() {
+                    return Err(ParseError::at(
+                        input,
+                        &line[i * 4..],
+                        format!("at most {} stacks", stacks.stacks.len()),
+                    ));
+                }
+
+                stacks.add(i, *c);
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// Parses the rearrangement procedure, one command per line.
+pub fn parse_commands(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_all(input, lines(command))
+}
+
+/// Writes the drawing and procedure back out as [`Day05`] parses them.
+pub fn render(layout: &Layout, commands: &[Command]) -> String {
+    let commands: Vec<String> = commands.iter().map(Command::to_string).collect();
+    format!("{}\n\n{}", layout, commands.join("\n"))
+}
+
+/// Finds the crates left on top after the rearrangement.
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    /// Starting stacks and the rearrangement procedure.
+    type Input = (Layout, Vec<Command>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (drawing, procedure) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::at(
+                input,
+                &input[input.len()..],
+                "a blank line between the stacks and the procedure",
+            )
+        })?;
+
+        let stacks = parse_stacks(drawing).map_err(|err| err.within(input, drawing))?;
+        let commands = parse_commands(procedure).map_err(|err| err.within(input, procedure))?;
+
+        for (cmd, line) in commands.iter().zip(procedure.lines()) {
+            if cmd.from.max(cmd.to) >= stacks.stacks.len() {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("stack numbers from 1 to {}", stacks.stacks.len()),
+                ));
+            }
+        }
+
+        Ok((stacks, commands))
+    }
+
+    /// Moves crates one at a time.
+    fn part1((stacks, commands): &Self::Input) -> Self::Part1 {
+        let mut stacks = stacks.clone();
+        for cmd in commands {
+            stacks.cmd_move(*cmd);
+        }
+
+        stacks.tops()
+    }
+
+    /// Moves crates in bulk, keeping their order.
+    fn part2((stacks, commands): &Self::Input) -> Self::Part2 {
+        let mut stacks = stacks.clone();
+        for cmd in commands {
+            stacks.cmd_bulk_move(*cmd);
+        }
+
+        stacks.tops()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn layout() -> impl Strategy<Value = Layout> {
+        let stack = prop::collection::vec(prop::char::range('A', 'Z'), 0..8);
+        prop::collection::vec(stack, 1..=9).prop_map(Layout::from)
+    }
+
+    fn command(stacks: usize) -> impl Strategy<Value = Command> {
+        (0..stacks, 0..stacks, 0..100usize).prop_map(|(from, to, count)| Command {
+            from,
+            to,
+            count,
+        })
+    }
+
+    fn procedure() -> impl Strategy<Value = (Layout, Vec<Command>)> {
+        layout().prop_flat_map(|layout| {
+            let stacks = layout.stacks().len();
+            (Just(layout), prop::collection::vec(command(stacks), 1..20))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_commands(command in command(9)) {
+            let text = command.to_string();
+            prop_assert_eq!(Command::try_from(text.as_str()), Ok(command));
+        }
+
+        #[test]
+        fn round_trips_drawings(layout in layout()) {
+            prop_assert_eq!(parse_stacks(&layout.to_string()), Ok(layout));
+        }
+
+        #[test]
+        fn round_trips_input((layout, commands) in procedure()) {
+            let input = aoc_core::input::normalize(&render(&layout, &commands));
+            prop_assert_eq!(Day05::parse(&input), Ok((layout, commands)));
+        }
+    }
+
+    #[test]
+    fn parse_row() {
+        assert_eq!(
+            stack_row("[N] [C]    ").unwrap().1,
+            vec![Some('N'), Some('C'), None],
+        );
+
+        assert_eq!(
+            stack_row("    [D]    ").unwrap().1,
+            vec![None, Some('D'), None],
+        );
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            Command::try_from("move 1 from 2 to 1").unwrap(),
+            Command {
+                from: 1,
+                to: 0,
+                count: 1,
+            },
+        );
+    }
+
+    #[test]
+    fn draws_rearranged_stacks() {
+        let input = aoc_core::input::normalize(include_str!("../examples/example.txt"));
+        let (layout, commands) = Day05::parse(&input).unwrap();
+        insta::assert_snapshot!("start", layout);
+
+        let mut one_at_a_time = layout.clone();
+        let mut in_bulk = layout;
+        for cmd in commands {
+            one_at_a_time.cmd_move(cmd);
+            in_bulk.cmd_bulk_move(cmd);
+        }
+        insta::assert_snapshot!("one_at_a_time", one_at_a_time);
+        insta::assert_snapshot!("in_bulk", in_bulk);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let err = Day05::parse("[A]\n 1 \n\nmove 1 from 1 to 2").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+
+        let err = Day05::parse("[A]\n 1 \n\nmove 1 from 0 to 1").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (4, 13, "0"));
+
+        let err = Day05::parse("[A] {B}\n 1   2 \n\nmove 1 from 1 to 2").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 5, "{"));
+
+        let err = Day05::parse("[A]\n 1 \nmove 1 from 1 to 1").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+}
@@ -0,0 +1,183 @@
+//! Day 6: Tuning Trouble.
+//!
+//! ```
+//! assert_eq!(aoc_06::detect_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), Some(7));
+//! ```
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+
+use aoc_core::{ParseError, Solution};
+use aoc_parse::{chars, parse_all};
+use serde::Deserialize;
+
+/// Puzzle constants, read from the `[day06]` section of the workspace
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Distinct characters in a start-of-packet marker.
+    pub packet_marker: NonZeroUsize,
+    /// Distinct characters in a start-of-message marker.
+    pub message_marker: NonZeroUsize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            packet_marker: NonZeroUsize::new(4).unwrap(),
+            message_marker: NonZeroUsize::new(14).unwrap(),
+        }
+    }
+}
+
+impl aoc_core::config::Section for Config {}
+
+impl Config {
+    /// The installed configuration, or the puzzle's constants if there is
+    /// none.
+    pub fn load() -> Self {
+        aoc_core::config::get(Day06::DAY)
+    }
+}
+
+/// Whether no item appears twice in `items`.
+pub fn all_unique<T: Eq + Hash>(items: &[T]) -> bool {
+    let mut seen = HashSet::with_capacity(items.len());
+
+    items.iter().all(|item| seen.insert(item))
+}
+
+/// Finds the position just past the first `size` consecutive distinct
+/// characters, if there are any.
+pub fn detect_marker(input: &str, size: usize) -> Option<usize> {
+    let chars: Vec<char> = input.chars().collect();
+
+    chars
+        .windows(size)
+        .position(all_unique)
+        .map(|start| start + size)
+}
+
+/// The ends of the markers found in a datastream buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Markers {
+    /// Position just past the start-of-packet marker.
+    pub packet: usize,
+    /// Position just past the start-of-message marker.
+    pub message: usize,
+}
+
+/// Locks on to the communication device's signal.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    /// The markers found in the datastream buffer.
+    type Input = Markers;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let buffer = parse_all(
+            input,
+            chars("a single line of lowercase letters", |c| {
+                c.is_ascii_lowercase()
+            }),
+        )?;
+
+        let config = Config::load();
+        let find = |marker, size: NonZeroUsize| {
+            detect_marker(buffer, size.get()).ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    &input[input.len()..],
+                    format!("a {} marker of {} distinct characters", marker, size),
+                )
+            })
+        };
+
+        Ok(Markers {
+            packet: find("start-of-packet", config.packet_marker)?,
+            message: find("start-of-message", config.message_marker)?,
+        })
+    }
+
+    /// The end of the start-of-packet marker.
+    fn part1(markers: &Self::Input) -> Self::Part1 {
+        markers.packet
+    }
+
+    /// The end of the start-of-message marker.
+    fn part2(markers: &Self::Input) -> Self::Part2 {
+        markers.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_marker() {
+        assert_eq!(detect_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+
+        assert_eq!(detect_marker("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+
+        assert_eq!(
+            detect_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4),
+            Some(10)
+        );
+
+        assert_eq!(
+            detect_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn finds_message_marker() {
+        assert_eq!(
+            detect_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14),
+            Some(19)
+        );
+        assert_eq!(detect_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), Some(23));
+        assert_eq!(detect_marker("nppdvjthqldpwncqszvftbrmjlhg", 14), Some(23));
+        assert_eq!(
+            detect_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14),
+            Some(29)
+        );
+        assert_eq!(
+            detect_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14),
+            Some(26)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_buffer() {
+        let err = Day06::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb\nabc").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 31));
+
+        let err = Day06::parse("mjqjpqm").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 8, ""));
+
+        let err = Day06::parse("aaaaaaaaaaaaaaaa").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 17));
+        assert_eq!(
+            err.expected,
+            "a start-of-packet marker of 4 distinct characters"
+        );
+        assert_eq!(
+            Day06::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
+            Ok(Markers {
+                packet: 7,
+                message: 19
+            })
+        );
+        assert_eq!(detect_marker("mjqjpqm", 4), Some(7));
+        assert_eq!(detect_marker("mjqjpqm", 14), None);
+    }
+}
@@ -1,58 +1,13 @@
-use std::collections::HashSet;
-use std::io::prelude::*;
-use std::io::stdin;
-
-fn all_unique(input: &str) -> bool {
-    let mut seen = HashSet::new();
-
-    for c in input.chars() {
-        if seen.contains(&c) {
-            return false;
-        }
-
-        seen.insert(c);
-    }
-
-    true
-}
-
-fn detect_marker(input: &str) -> usize {
-    let mut prev_chunk = input.chars().take(3).collect::<String>();
-
-    for (i, c) in input.chars().skip(3).enumerate() {
-        prev_chunk.push(c);
-
-        if all_unique(prev_chunk.as_str()) {
-            return i + 4;
-        }
-
-        prev_chunk.remove(0);
-    }
-
-    unreachable!("no marker found")
-}
+use aoc_core::Solution;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input)?;
+    aoc_core::log::init(None)?;
+    let mut settings = aoc_core::config::Settings::load(None, &[])?;
+    settings.check::<aoc_06::Config>(aoc_06::Day06::DAY)?;
+    settings.install();
+    let input = aoc_core::input::load(aoc_06::Day06::DAY)?;
 
-    println!("Marker start - {}", detect_marker(&input));
+    print!("{}", aoc_core::solve::<aoc_06::Day06>(&input)?);
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn finds_marker() {
-        assert_eq!(detect_marker("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5,);
-
-        assert_eq!(detect_marker("nppdvjthqldpwncqszvftbrmjlhg"), 6,);
-
-        assert_eq!(detect_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10,);
-
-        assert_eq!(detect_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11,);
-    }
-}
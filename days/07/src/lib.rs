@@ -0,0 +1,473 @@
+//! Day 7: No Space Left On Device.
+//!
+//! ```
+//! let fs = aoc_07::parse_shellout("$ cd /\n$ ls\ndir a\n100 b.txt\n$ cd a\n$ ls\n50 c").unwrap();
+//! assert_eq!(fs.tree_size(), 150);
+//! assert_eq!(fs.children().len(), 2);
+//! ```
+
+use std::fmt;
+use std::path::{Component, Components, PathBuf};
+
+use aoc_core::{json, ParseError, Solution, Value};
+use aoc_parse::{integer, lines, literal, parse_all, IResult};
+use nom::branch::alt;
+use nom::character::complete::{char, not_line_ending};
+use nom::combinator::{consumed, map, value, verify};
+use nom::error::context;
+use nom::sequence::{preceded, separated_pair};
+use serde::Deserialize;
+
+/// Puzzle constants, read from the `[day07]` section of the workspace
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Total space on the device's disk.
+    pub disk_size: usize,
+    /// Unused space the update needs.
+    pub update_size: usize,
+    /// Largest size of a directory counted in part 1.
+    pub small_directory: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            disk_size: 70_000_000,
+            update_size: 30_000_000,
+            small_directory: 100_000,
+        }
+    }
+}
+
+impl aoc_core::config::Section for Config {
+    /// Deleting the root directory frees up everything, so it is always a
+    /// candidate unless the update is larger than the disk.
+    fn validate(&self) -> Result<(), String> {
+        if self.update_size > self.disk_size {
+            return Err(format!(
+                "`update_size` {} does not fit on a disk of `disk_size` {}",
+                self.update_size, self.disk_size
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Config {
+    /// The installed configuration, or the puzzle's constants if there is
+    /// none.
+    pub fn load() -> Self {
+        aoc_core::config::get(Day07::DAY)
+    }
+}
+
+/// A directory and everything below it.
+#[derive(Debug)]
+pub struct Directory {
+    name: String,
+    children: Vec<Node>,
+}
+
+impl Directory {
+    /// Creates an empty directory.
+    pub fn new(name: &str) -> Directory {
+        Directory {
+            name: name.to_string(),
+            children: Vec::new(),
+        }
+    }
+
+    /// The directory's name; `/` for the root.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Files and directories directly inside this directory.
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// Total size of every file below this directory.
+    pub fn tree_size(&self) -> usize {
+        self.children
+            .iter()
+            .map(|node| match node {
+                Node::Directory(d) => d.tree_size(),
+                Node::File(f) => f.size,
+            })
+            .sum()
+    }
+
+    /// Collects the total size of this directory and every directory below it.
+    pub fn directory_sizes(&self, acc: &mut Vec<(usize, String)>) {
+        let mut dir_size = 0;
+        for child in self.children.iter() {
+            match child {
+                Node::File(f) => {
+                    dir_size += f.size;
+                }
+                Node::Directory(d) => {
+                    dir_size += d.tree_size();
+                    d.directory_sizes(acc)
+                }
+            }
+        }
+
+        acc.push((dir_size, self.name.clone()));
+    }
+
+    fn add_node(&mut self, mut components: Components, node: Node) -> bool {
+        if let Some(component) = components.next() {
+            let dir = self.children.iter_mut().find(|node| match component {
+                Component::Normal(os) => os == node.name(),
+                _ => false,
+            });
+
+            return match dir {
+                Some(Node::Directory(dir)) => dir.add_node(components, node),
+                _ => false,
+            };
+        }
+
+        self.children.push(node);
+
+        true
+    }
+}
+
+impl Directory {
+    fn write_tree(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}- {} (dir)", "", self.name, indent = depth * 2)?;
+
+        for child in &self.children {
+            writeln!(f)?;
+            match child {
+                Node::Directory(d) => d.write_tree(f, depth + 1)?,
+                Node::File(file) => write!(
+                    f,
+                    "{:indent$}- {} (file, size={})",
+                    "",
+                    file.name,
+                    file.size,
+                    indent = (depth + 1) * 2
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Directory {
+    /// Draws the tree below the directory as in the puzzle, one entry per
+    /// line, indented by depth.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+
+/// An entry in a directory.
+#[derive(Debug)]
+pub enum Node {
+    File(File),
+    Directory(Box<Directory>),
+}
+
+impl Node {
+    /// The entry's name.
+    pub fn name(&self) -> &str {
+        match self {
+            Node::File(f) => f.name.as_str(),
+            Node::Directory(d) => d.name.as_str(),
+        }
+    }
+}
+
+/// A file and its size.
+#[derive(Debug)]
+pub struct File {
+    pub size: usize,
+    pub name: String,
+}
+
+/// Replays shell commands to build up the filesystem they explored.
+pub struct DirectoryBuilder {
+    /// Current shell directory context.
+    context: PathBuf,
+    directory: Directory,
+}
+
+impl Default for DirectoryBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DirectoryBuilder {
+    /// Starts at the root of an empty filesystem.
+    pub fn new() -> Self {
+        Self {
+            context: "/".into(),
+            directory: Directory::new("/"),
+        }
+    }
+
+    /// Changes the current directory, as `cd` would.
+    pub fn set_context(&mut self, dir: &str) {
+        match dir {
+            "." => (),
+            ".." => {
+                self.context.pop();
+            }
+            "/" => self.context = "/".into(),
+            p => self.context.push(p),
+        }
+
+        tracing::trace!(context = %self.context.display(), "cd");
+    }
+
+    /// Adds an entry to the current directory. Returns `false` if the current
+    /// directory has not been listed yet, or names a file.
+    pub fn add_node(&mut self, node: Node) -> bool {
+        let mut components = self.context.as_path().components();
+        // dump root directory.
+        components.next().unwrap();
+        let name = node.name().to_string();
+        let added = self.directory.add_node(components, node);
+
+        tracing::debug!(context = %self.context.display(), name, added, "add node");
+        added
+    }
+
+    /// Returns the root of the filesystem built so far.
+    pub fn into_directory(self) -> Directory {
+        self.directory
+    }
+}
+
+/// A line of the shell transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line<'a> {
+    /// `$ cd <dir>`
+    Cd(&'a str),
+    /// `$ ls`
+    Ls,
+    /// `dir <name>`, listed by `ls`.
+    Dir(&'a str),
+    /// `<size> <name>`, listed by `ls`.
+    File(usize, &'a str),
+}
+
+/// Parses a line of the transcript: a command after a `$` prompt, or a line
+/// of `ls` output.
+pub fn line(input: &str) -> IResult<'_, Line<'_>> {
+    let name = || {
+        context(
+            "a name",
+            verify(not_line_ending, |name: &str| !name.is_empty()),
+        )
+    };
+
+    alt((
+        preceded(
+            literal("$ "),
+            context(
+                "`cd <dir>` or `ls`",
+                alt((
+                    map(preceded(literal("cd "), name()), Line::Cd),
+                    value(Line::Ls, literal("ls")),
+                )),
+            ),
+        ),
+        context(
+            "`dir <name>` or `<size> <name>`",
+            alt((
+                map(preceded(literal("dir "), name()), Line::Dir),
+                map(
+                    separated_pair(context("`dir` or a file size", integer), char(' '), name()),
+                    |(size, name)| Line::File(size, name),
+                ),
+            )),
+        ),
+    ))(input)
+}
+
+/// Rebuilds the filesystem explored by the shell transcript.
+pub fn parse_shellout(input: &str) -> Result<Directory, ParseError> {
+    let transcript = parse_all(input, lines(consumed(line)))?;
+    let mut filesystem = DirectoryBuilder::new();
+    let mut listing = false;
+
+    for (text, line) in transcript {
+        let node = match line {
+            Line::Cd(dir) => {
+                filesystem.set_context(dir);
+                listing = false;
+                continue;
+            }
+            Line::Ls => {
+                listing = true;
+                continue;
+            }
+            Line::Dir(name) => Node::Directory(Box::new(Directory::new(name))),
+            Line::File(size, name) => Node::File(File {
+                size,
+                name: name.into(),
+            }),
+        };
+
+        if !listing {
+            return Err(ParseError::at(input, text, "a `$` command"));
+        }
+        if !filesystem.add_node(node) {
+            return Err(ParseError::at(
+                input,
+                text,
+                "a listing of a directory seen in an earlier `ls`",
+            ));
+        }
+    }
+
+    Ok(filesystem.into_directory())
+}
+
+/// Directories holding at most the configured small size, with their sizes.
+pub fn small_directories(fs: &Directory, config: &Config) -> Vec<(usize, String)> {
+    let mut sizes = vec![];
+    fs.directory_sizes(&mut sizes);
+
+    sizes.retain(|(size, _)| *size <= config.small_directory);
+    sizes
+}
+
+/// Directories large enough that deleting one frees up the space the update
+/// needs, with their sizes.
+pub fn deletion_candidates(fs: &Directory, config: &Config) -> Vec<(usize, String)> {
+    let unused_space = config.disk_size.saturating_sub(fs.tree_size());
+    let required_space = config.update_size.saturating_sub(unused_space);
+
+    let mut sizes = vec![];
+    fs.directory_sizes(&mut sizes);
+
+    sizes.retain(|(size, _)| *size >= required_space);
+    sizes
+}
+
+/// Finds directories to delete to make room for the system update.
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    /// Filesystem rebuilt from the shell transcript.
+    type Input = Directory;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_shellout(input)
+    }
+
+    /// Sums the sizes of every directory holding at most the small directory
+    /// size, 100,000 in the puzzle.
+    fn part1(fs: &Self::Input) -> Self::Part1 {
+        small_directories(fs, &Config::load())
+            .iter()
+            .map(|(size, _)| *size)
+            .sum()
+    }
+
+    /// Finds the size of the smallest directory that frees up enough space for
+    /// the update.
+    fn part2(fs: &Self::Input) -> Self::Part2 {
+        deletion_candidates(fs, &Config::load())
+            .iter()
+            .map(|(size, _)| *size)
+            .min()
+            .expect("the configuration checked the root directory frees enough")
+    }
+
+    /// The directories each part chose from.
+    fn details(fs: &Self::Input) -> Value {
+        let config = Config::load();
+        let listing = |dirs: Vec<(usize, String)>| {
+            dirs.into_iter()
+                .map(|(size, name)| json!({ "name": name, "size": size }))
+                .collect::<Vec<_>>()
+        };
+
+        json!({
+            "small_directories": listing(small_directories(fs, &config)),
+            "deletion_candidates": listing(deletion_candidates(fs, &config)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::input::normalize;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    #[test]
+    fn lists_deletion_candidates() {
+        let fs = parse_shellout(&normalize(INPUT)).unwrap();
+
+        assert_eq!(
+            Day07::details(&fs)["deletion_candidates"],
+            json!([
+                { "name": "d", "size": 24933642 },
+                { "name": "/", "size": 48381165 },
+            ])
+        );
+    }
+
+    #[test]
+    fn frees_space_on_other_disks() {
+        let fs = parse_shellout(&normalize(INPUT)).unwrap();
+        let config = Config {
+            disk_size: 80_000_000,
+            small_directory: 1_000,
+            ..Config::default()
+        };
+
+        assert_eq!(small_directories(&fs, &config), [(584, "e".to_string())]);
+        assert_eq!(deletion_candidates(&fs, &config).len(), 4);
+
+        let config = Config {
+            update_size: 90_000_000,
+            ..config
+        };
+        assert!(aoc_core::config::Section::validate(&config).is_err());
+    }
+
+    #[test]
+    fn draws_directory_tree() {
+        let fs = parse_shellout(&normalize(INPUT)).unwrap();
+
+        insta::assert_snapshot!(fs.to_string());
+    }
+
+    #[test]
+    fn rejects_malformed_transcript() {
+        let err = parse_shellout("$ cd /\n$ dir").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "dir"));
+
+        let err = parse_shellout("$ ls\n12k a.txt").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 1, "12k"));
+
+        let err = parse_shellout("$ cd a\n$ ls\n10 b").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let err = parse_shellout("ls").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+
+        let err = parse_shellout("$ cd /\n10 b").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a `$` command"));
+    }
+}
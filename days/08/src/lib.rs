@@ -0,0 +1,132 @@
+//! Day 8: Treetop Tree House.
+//!
+//! ```
+//! let grid = aoc_08::parse_grid("303\n255\n653").unwrap();
+//! assert_eq!(aoc_08::visible_trees(&grid), 9);
+//! ```
+
+use aoc_core::{ParseError, Solution};
+use aoc_geometry::Direction;
+use aoc_grid::{Grid, Pos};
+use aoc_parse::{grid, parse_all};
+
+/// Marks the trees visible from outside the grid.
+///
+/// A tree is visible if it is the tallest tree in at least one of four
+/// directions: left, right, up, or down.
+pub fn visibility(grid: &Grid<u32>) -> Grid<bool> {
+    let mut visible = Grid::new(grid.width(), grid.height(), false);
+    for pos in grid.positions() {
+        visible[pos] = Direction::ALL.into_iter().any(|direction| {
+            grid.ray(pos, direction.step())
+                .all(|tree| grid[tree] < grid[pos])
+        });
+    }
+
+    visible
+}
+
+/// Counts the trees visible from outside the grid.
+pub fn visible_trees(grid: &Grid<u32>) -> usize {
+    visibility(grid).iter().filter(|visible| **visible).count()
+}
+
+/// Counts the trees seen from `pos` looking in `direction`, stopping at the
+/// edge or at the first tree at least as tall.
+fn viewing_distance(grid: &Grid<u32>, pos: Pos, direction: Direction) -> usize {
+    let mut count = 0;
+    for tree in grid.ray(pos, direction.step()) {
+        count += 1;
+
+        if grid[tree] >= grid[pos] {
+            break;
+        }
+    }
+
+    count
+}
+
+/// Finds the highest scenic score, the product of the viewing distances in each
+/// direction, of any tree.
+pub fn near_visibility(grid: &Grid<u32>) -> u32 {
+    grid.positions()
+        .map(|pos| {
+            Direction::ALL
+                .into_iter()
+                .map(|direction| viewing_distance(grid, pos, direction))
+                .product::<usize>()
+        })
+        .max()
+        .unwrap() as u32
+}
+
+/// Parses the map of tree heights.
+pub fn parse_grid(input: &str) -> Result<Grid<u32>, ParseError> {
+    parse_all(
+        input,
+        grid("a map of tree heights", "a tree height digit", |c| {
+            c.to_digit(10)
+        }),
+    )
+}
+
+/// Scouts the forest for a tree house location.
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    /// Tree heights.
+    type Input = Grid<u32>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Self::Part1 {
+        visible_trees(grid)
+    }
+
+    fn part2(grid: &Self::Input) -> Self::Part2 {
+        near_visibility(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_malformed_grid() {
+        let err = parse_grid("303\n2a5").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "a"));
+
+        let err = parse_grid("303\n25").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        assert_eq!(
+            parse_grid("").unwrap_err().expected,
+            "a map of tree heights"
+        );
+    }
+
+    #[test]
+    fn marks_visible_trees() {
+        let grid = parse_grid(include_str!("../examples/example.txt")).unwrap();
+
+        assert_eq!(
+            visibility(&grid).render(|visible| if *visible { '#' } else { '.' }),
+            "#####\n###.#\n##.##\n#.#.#\n#####"
+        );
+    }
+
+    #[test]
+    fn stops_at_trees_of_equal_height() {
+        let grid = parse_grid("14351").unwrap();
+        assert_eq!(viewing_distance(&grid, (1, 0), Direction::Right), 2);
+        assert_eq!(viewing_distance(&grid, (3, 0), Direction::Left), 3);
+    }
+}
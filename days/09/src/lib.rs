@@ -0,0 +1,678 @@
+//! Day 9: Rope Bridge.
+//!
+//! ```
+//! use aoc_09::{parse, simulate, Map};
+//!
+//! let rope = simulate(Map::new(), &parse("R 4\nU 4").unwrap());
+//! assert_eq!(rope.visited().len(), 7);
+//! ```
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use aoc_core::{Lines, ParseError, Solution, StreamError, Streaming};
+use aoc_geometry::{Direction, Point};
+use aoc_grid::Grid;
+use aoc_parse::{integer, lines, parse_all, IResult};
+use nom::character::complete::{alpha1, char};
+use nom::combinator::{map_res, verify};
+use nom::error::context;
+use nom::sequence::{pair, preceded};
+use serde::Deserialize;
+
+/// Puzzle constants, read from the `[day09]` section of the workspace
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Knots in part 2's rope, head included.
+    pub knots: Knots,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { knots: Knots(10) }
+    }
+}
+
+impl aoc_core::config::Section for Config {}
+
+/// Number of knots in a rope, head included: at least two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "usize")]
+pub struct Knots(usize);
+
+impl Knots {
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl TryFrom<usize> for Knots {
+    type Error = String;
+
+    fn try_from(knots: usize) -> Result<Self, Self::Error> {
+        if knots < 2 {
+            return Err(format!("a rope has at least 2 knots, not {}", knots));
+        }
+
+        Ok(Self(knots))
+    }
+}
+
+impl Config {
+    /// The installed configuration, or the puzzle's constants if there is
+    /// none.
+    pub fn load() -> Self {
+        aoc_core::config::get(Day09::DAY)
+    }
+
+    /// Part 2's rope, before its head moves.
+    pub fn long_rope(&self) -> Map {
+        Map::n_tails(self.knots.get() - 1)
+    }
+}
+
+/// A motion of the rope's head.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Command {
+    pub direction: Direction,
+    pub steps: i32,
+}
+
+/// Positions visited by a knot.
+#[derive(Debug, Clone, Default)]
+pub struct Visited(HashSet<Point>);
+
+impl Visited {
+    /// No positions visited yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct positions visited.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no position has been visited.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Every visited position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Draws the visited positions as `#` on `.`, cropped to them, with the
+    /// start marked `s` as in the puzzle.
+    pub fn render(&self) -> String {
+        let Some(first) = self.iter().next() else {
+            return String::new();
+        };
+        let (start, end) = self.iter().fold((first, first), |(start, end), pos| {
+            (
+                Point::new(start.x.min(pos.x), start.y.min(pos.y)),
+                Point::new(end.x.max(pos.x), end.y.max(pos.y)),
+            )
+        });
+
+        let size = end - start + Point::new(1, 1);
+        let mut trail = Grid::new(size.x as usize, size.y as usize, '.');
+        for pos in self.iter().chain([Point::ORIGIN]) {
+            let offset = pos - start;
+            if let Some(cell) = trail.get_mut((offset.x as usize, offset.y as usize)) {
+                *cell = if pos == Point::ORIGIN { 's' } else { '#' };
+            }
+        }
+
+        trail.render(|c| *c)
+    }
+
+    /// Whether `pos` has been visited.
+    pub fn contains(&self, pos: Point) -> bool {
+        self.0.contains(&pos)
+    }
+
+    /// Marks `pos` as visited, returning whether it was new.
+    pub fn insert(&mut self, pos: Point) -> bool {
+        self.0.insert(pos)
+    }
+}
+
+/// A segment of rope between two knots. Longer ropes chain segments together,
+/// with each segment's head following the previous segment's tail.
+#[derive(Debug, Clone)]
+pub struct Map {
+    head: Point,
+    tail: Point,
+    next: Option<Box<Map>>,
+    map: Visited,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Map {
+    /// A rope with two knots, both at the origin.
+    pub fn new() -> Self {
+        Self {
+            head: Point::ORIGIN,
+            tail: Point::ORIGIN,
+            map: Visited::new(),
+            next: None,
+        }
+    }
+
+    /// A rope made of `n` segments, so `n + 1` knots.
+    pub fn n_tails(n: usize) -> Self {
+        let mut map = Self {
+            head: Point::ORIGIN,
+            tail: Point::ORIGIN,
+            map: Visited::new(),
+            next: None,
+        };
+
+        if n > 1 {
+            map.next = Some(Box::new(Map::n_tails(n - 1)));
+        }
+
+        map
+    }
+
+    /// Number of knots in the rope, head included.
+    pub fn knots(&self) -> usize {
+        if let Some(next) = &self.next {
+            return 1 + next.knots();
+        }
+
+        2
+    }
+
+    /// Position of every knot, head first.
+    pub fn positions(&self) -> Vec<Point> {
+        let mut positions = vec![self.head];
+        let mut segment = self;
+        loop {
+            positions.push(segment.tail);
+            match &segment.next {
+                Some(next) => segment = next,
+                None => return positions,
+            }
+        }
+    }
+
+    /// The last segment of the rope.
+    pub fn tail(&self) -> &Map {
+        if let Some(next) = &self.next {
+            return next.tail();
+        }
+
+        self
+    }
+
+    /// Positions visited by this segment's tail knot. Only the rope's last
+    /// segment keeps track of them; the others are always empty.
+    pub fn visited(&self) -> &Visited {
+        &self.map
+    }
+
+    /// Moves the head step by step, dragging the rest of the rope along.
+    pub fn move_head(&mut self, cmd: &Command) {
+        for _ in 0..cmd.steps {
+            self.head += cmd.direction.step();
+            self.move_tail();
+        }
+    }
+
+    /// Once the head is no longer touching the tail, the tail takes a single
+    /// step towards it, diagonally if they are in different rows and columns.
+    fn move_tail(&mut self) {
+        if self.head.chebyshev(self.tail) > 1 {
+            self.tail += (self.head - self.tail).signum();
+        }
+
+        match &mut self.next {
+            Some(next) => {
+                next.head = self.tail;
+                next.move_tail()
+            }
+            None => {
+                self.map.insert(self.tail);
+            }
+        }
+    }
+}
+
+impl TryFrom<&str> for Command {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        parse_all(line, command)
+    }
+}
+
+/// Parses a motion like `R 4`.
+pub fn command(input: &str) -> IResult<'_, Command> {
+    let (rest, (direction, steps)) = pair(
+        context("`U`, `D`, `L` or `R`", map_res(alpha1, Direction::try_from)),
+        context(
+            "a number of steps",
+            preceded(char(' '), verify(integer::<i32>, |steps| *steps >= 0)),
+        ),
+    )(input)?;
+
+    Ok((rest, Command { direction, steps }))
+}
+
+impl fmt::Display for Command {
+    /// Writes the motion as in the puzzle, e.g. `R 4`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.steps)
+    }
+}
+
+/// Parses the head's motions, one per line.
+pub fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_all(input, lines(command))
+}
+
+/// Applies every motion to the rope.
+pub fn simulate(mut map: Map, cmds: &[Command]) -> Map {
+    for cmd in cmds {
+        map.move_head(cmd);
+    }
+
+    map
+}
+
+/// Tracks the knots of a rope as its head moves.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    /// Motions of the rope's head.
+    type Input = Vec<Command>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    /// Counts the positions visited by the tail of a two knot rope.
+    fn part1(cmds: &Self::Input) -> Self::Part1 {
+        simulate(Map::new(), cmds).map.len()
+    }
+
+    /// Counts the positions visited by the tail of the long rope, ten knots
+    /// in the puzzle.
+    fn part2(cmds: &Self::Input) -> Self::Part2 {
+        simulate(Config::load().long_rope(), cmds).tail().map.len()
+    }
+}
+
+impl Streaming for Day09 {
+    /// Moves both ropes as each motion is read.
+    fn stream(lines: Lines<'_>) -> Result<(Self::Part1, Self::Part2), StreamError> {
+        let (mut short, mut long) = (Map::new(), Config::load().long_rope());
+
+        for line in lines {
+            let line = line?;
+            let cmd =
+                Command::try_from(line.text.as_str()).map_err(|err| err.on_line(line.number))?;
+
+            short.move_head(&cmd);
+            long.move_head(&cmd);
+        }
+
+        Ok((short.visited().len(), long.tail().visited().len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::input::normalize;
+    use proptest::prelude::*;
+
+    fn command() -> impl Strategy<Value = Command> {
+        (prop::sample::select(Direction::ALL.to_vec()), 0..=i32::MAX)
+            .prop_map(|(direction, steps)| Command { direction, steps })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_commands(cmds in prop::collection::vec(command(), 1..50)) {
+            let input: Vec<String> = cmds.iter().map(Command::to_string).collect();
+            prop_assert_eq!(parse(&input.join("\n")), Ok(cmds));
+        }
+    }
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    #[test]
+    fn parses_input() {
+        assert_eq!(
+            parse(&normalize(INPUT)).unwrap(),
+            vec![
+                Command {
+                    direction: Direction::Right,
+                    steps: 4
+                },
+                Command {
+                    direction: Direction::Up,
+                    steps: 4
+                },
+                Command {
+                    direction: Direction::Left,
+                    steps: 3
+                },
+                Command {
+                    direction: Direction::Down,
+                    steps: 1
+                },
+                Command {
+                    direction: Direction::Right,
+                    steps: 4
+                },
+                Command {
+                    direction: Direction::Down,
+                    steps: 1
+                },
+                Command {
+                    direction: Direction::Left,
+                    steps: 5
+                },
+                Command {
+                    direction: Direction::Right,
+                    steps: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn move_back_and_forth() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Down,
+                steps: 2,
+            },
+            Command {
+                direction: Direction::Down,
+                steps: 1,
+            },
+            Command {
+                direction: Direction::Up,
+                steps: 1,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 3);
+    }
+
+    #[test]
+    fn moves_laterally() {
+        let cmds = vec![Command {
+            direction: Direction::Down,
+            steps: 6,
+        }];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 6);
+    }
+
+    #[test]
+    fn moves_diagonally() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Right,
+                steps: 1,
+            },
+            Command {
+                direction: Direction::Up,
+                steps: 2,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 2);
+    }
+
+    #[test]
+    fn moves_diagonally_left() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Up,
+                steps: 4,
+            },
+            Command {
+                direction: Direction::Left,
+                steps: 3,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 6);
+    }
+
+    #[test]
+    fn moves_diagonally_negative() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Left,
+                steps: 1,
+            },
+            Command {
+                direction: Direction::Down,
+                steps: 2,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 2);
+    }
+
+    #[test]
+    fn diagonal_move_not_far_enough() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Left,
+                steps: 1,
+            },
+            Command {
+                direction: Direction::Down,
+                steps: 1,
+            },
+            Command {
+                direction: Direction::Up,
+                steps: 2,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 1);
+    }
+
+    #[test]
+    fn reverse_course() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Left,
+                steps: 1,
+            },
+            Command {
+                direction: Direction::Right,
+                steps: 4,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 3);
+    }
+
+    #[test]
+    fn circle() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Right,
+                steps: 5,
+            },
+            Command {
+                direction: Direction::Up,
+                steps: 5,
+            },
+            Command {
+                direction: Direction::Left,
+                steps: 5,
+            },
+            Command {
+                direction: Direction::Down,
+                steps: 5,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 17);
+    }
+
+    #[test]
+    fn counter_circle() {
+        let cmds = vec![
+            Command {
+                direction: Direction::Left,
+                steps: 5,
+            },
+            Command {
+                direction: Direction::Down,
+                steps: 5,
+            },
+            Command {
+                direction: Direction::Right,
+                steps: 5,
+            },
+            Command {
+                direction: Direction::Up,
+                steps: 5,
+            },
+        ];
+
+        let mut map = Map::new();
+        for cmd in cmds {
+            map.move_head(&cmd);
+        }
+
+        assert_eq!(map.map.len(), 17);
+    }
+
+    #[test]
+    fn builds_configured_rope() {
+        for knots in [10, 3, 2] {
+            let config = Config {
+                knots: Knots::try_from(knots).unwrap(),
+            };
+            assert_eq!(config.long_rope().knots(), knots);
+        }
+
+        assert!(Knots::try_from(1).is_err() && Knots::try_from(0).is_err());
+    }
+
+    #[test]
+    fn draws_tail_trails() {
+        let cmds = parse(&normalize(INPUT)).unwrap();
+        let map = simulate(Map::new(), &cmds);
+        insta::assert_snapshot!("short_rope", map.visited().render());
+
+        let cmds = parse(&normalize(include_str!("../examples/larger.txt"))).unwrap();
+        let map = simulate(Map::n_tails(9), &cmds);
+        insta::assert_snapshot!("long_rope", map.tail().visited().render());
+    }
+
+    #[test]
+    fn tracks_visited_positions() {
+        let mut visited = Visited::new();
+
+        for pos in [(0, 0), (3, -2), (-5, 7), (3, -2), (-40, -40), (0, 0)] {
+            visited.insert(pos.into());
+        }
+
+        assert_eq!(visited.len(), 4);
+        assert!(visited.contains(Point::new(3, -2)) && visited.contains(Point::new(-40, -40)));
+        assert!(!visited.contains(Point::new(1, 1)) && !visited.contains(Point::new(100, 0)));
+
+        let mut positions: Vec<Point> = visited.iter().collect();
+        positions.sort_by_key(|pos| (pos.y, pos.x));
+        assert_eq!(
+            positions,
+            vec![
+                Point::new(-40, -40),
+                Point::new(3, -2),
+                Point::ORIGIN,
+                Point::new(-5, 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn streams_input() {
+        let input = INPUT.replace('\n', "\r\n");
+
+        assert_eq!(
+            aoc_core::solve_stream::<Day09>(&mut input.as_bytes()).unwrap(),
+            aoc_core::solve::<Day09>(INPUT).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_motions() {
+        let err = parse("R 4\nX 4").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 1, "X"));
+
+        let err = parse("R 4\nU -2").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "-2"));
+
+        let err = parse("R").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 2, ""));
+    }
+}
@@ -0,0 +1,356 @@
+//! Day 10: Cathode-Ray Tube.
+//!
+//! ```
+//! let cmds = aoc_10::parse("noop\naddx 3\naddx -5").unwrap();
+//! let vm = aoc_10::execute(&aoc_10::Config::default(), &cmds);
+//! assert_eq!(vm.register(), -1);
+//! ```
+
+use std::fmt;
+use std::num::NonZeroUsize;
+
+use aoc_core::{Lines, ParseError, Solution, StreamError, Streaming};
+use aoc_grid::Grid;
+use aoc_parse::{integer, lines, literal, parse_all, IResult};
+use nom::branch::alt;
+use nom::combinator::{map, value};
+use nom::error::context;
+use nom::sequence::preceded;
+use serde::Deserialize;
+
+/// Puzzle constants, read from the `[day10]` section of the workspace
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Pixels in each row of the CRT.
+    pub width: NonZeroUsize,
+    /// Rows of pixels on the CRT.
+    pub height: NonZeroUsize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: NonZeroUsize::new(40).unwrap(),
+            height: NonZeroUsize::new(6).unwrap(),
+        }
+    }
+}
+
+impl aoc_core::config::Section for Config {}
+
+impl Config {
+    /// The installed configuration, or the puzzle's constants if there is
+    /// none.
+    pub fn load() -> Self {
+        aoc_core::config::get(Day10::DAY)
+    }
+}
+
+/// An instruction for the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// `addx V`, taking two cycles.
+    Add(i32),
+    /// The second cycle of an `addx`, while the CPU is busy.
+    Busy,
+    /// `noop`, taking one cycle.
+    Noop,
+}
+
+impl TryFrom<&str> for Command {
+    type Error = ParseError;
+
+    fn try_from(cmd: &str) -> Result<Self, Self::Error> {
+        parse_all(cmd, command)
+    }
+}
+
+/// Parses an instruction, `addx V` or `noop`.
+pub fn command(input: &str) -> IResult<'_, Command> {
+    context(
+        "`addx V` or `noop`",
+        alt((
+            map(
+                preceded(literal("addx "), context("a value to add", integer)),
+                Command::Add,
+            ),
+            value(Command::Noop, literal("noop")),
+        )),
+    )(input)
+}
+
+impl fmt::Display for Command {
+    /// Writes the instruction as in the puzzle. [`Command::Busy`] never
+    /// appears in a program; it is written as `noop`, as both only spend a
+    /// cycle.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Add(val) => write!(f, "addx {}", val),
+            Command::Busy | Command::Noop => write!(f, "noop"),
+        }
+    }
+}
+
+/// The handheld's CPU driving the CRT.
+#[derive(Debug, Clone)]
+pub struct VM {
+    register: i32,
+    next: Option<i32>,
+    pending: Option<i32>,
+    cycle: usize,
+    /// Sum of the signal strengths sampled during the 20th, 60th, 100th, 140th,
+    /// 180th and 220th cycles.
+    signal_strength: i64,
+    canvas: Grid<bool>,
+}
+
+impl Default for VM {
+    fn default() -> Self {
+        Self::new(&Config::default())
+    }
+}
+
+impl VM {
+    /// A CPU at its first cycle with the `X` register set to 1, driving a
+    /// blank CRT of the configured size.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            register: 1,
+            cycle: 1,
+            signal_strength: 0,
+            next: None,
+            pending: None,
+            canvas: Grid::new(config.width.get(), config.height.get(), false),
+        }
+    }
+
+    /// Runs a single instruction to completion.
+    pub fn execute(&mut self, cmd: Command) {
+        let _span = tracing::debug_span!("execute", %cmd, x = self.register).entered();
+        self.next = self.pending.take();
+        let sprite_span = std::ops::Range {
+            start: self.register - 1,
+            end: self.register + 2,
+        };
+
+        match cmd {
+            Command::Noop => {}
+            Command::Busy => {}
+            Command::Add(x) => {
+                self.pending = Some(x);
+                self.tick(&sprite_span);
+
+                return self.execute(Command::Busy);
+            }
+        }
+
+        self.tick(&sprite_span);
+        if let Some(x) = self.next.take() {
+            self.register += x;
+        }
+    }
+
+    /// Draws the pixel for the current cycle and advances the clock.
+    fn tick(&mut self, sprite_span: &std::ops::Range<i32>) {
+        if self.cycle <= 220 && self.cycle % 40 == 20 {
+            self.signal_strength += self.strength();
+        }
+
+        let x = (self.cycle - 1) % self.canvas.width();
+        let y = (self.cycle - 1) / self.canvas.width();
+        tracing::trace!(
+            cycle = self.cycle,
+            strength = self.strength(),
+            sprite = ?sprite_span,
+            x,
+            y,
+            "tick"
+        );
+        // Programs running past the last row keep computing off screen.
+        if let Some(pixel) = self.canvas.get_mut((x, y)) {
+            if sprite_span.contains(&(x as i32)) {
+                *pixel = true;
+            }
+        }
+
+        self.cycle += 1;
+    }
+
+    /// The signal strength during the current cycle, wide enough for any
+    /// register value on any cycle.
+    fn strength(&self) -> i64 {
+        i64::from(self.register) * self.cycle as i64
+    }
+
+    /// The cycle about to start, counting from 1.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Value of the `X` register.
+    pub fn register(&self) -> i32 {
+        self.register
+    }
+
+    /// Sum of the signal strengths sampled so far.
+    pub fn signal_strength(&self) -> i64 {
+        self.signal_strength
+    }
+
+    /// The CRT's pixels, lit or not.
+    pub fn canvas(&self) -> &Grid<bool> {
+        &self.canvas
+    }
+
+    /// Renders the CRT, one line per row.
+    pub fn render(&self) -> String {
+        self.canvas.render(|x| if *x { ' ' } else { '░' })
+    }
+}
+
+/// Parses the program, one instruction per line.
+pub fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_all(input, lines(command))
+}
+
+/// Runs the whole program on a fresh CPU.
+pub fn execute(config: &Config, cmds: &[Command]) -> VM {
+    let mut vm = VM::new(config);
+    for cmd in cmds {
+        vm.execute(*cmd);
+    }
+
+    vm
+}
+
+/// Emulates the handheld's CPU and CRT.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    /// The program's instructions.
+    type Input = Vec<Command>;
+    type Part1 = i64;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(cmds: &Self::Input) -> Self::Part1 {
+        execute(&Config::load(), cmds).signal_strength
+    }
+
+    /// Renders the image drawn on the CRT.
+    fn part2(cmds: &Self::Input) -> Self::Part2 {
+        execute(&Config::load(), cmds).render()
+    }
+}
+
+impl Streaming for Day10 {
+    /// Runs each instruction as it is read.
+    fn stream(lines: Lines<'_>) -> Result<(Self::Part1, Self::Part2), StreamError> {
+        let mut vm = VM::new(&Config::load());
+
+        for line in lines {
+            let line = line?;
+            vm.execute(
+                Command::try_from(line.text.as_str()).map_err(|err| err.on_line(line.number))?,
+            );
+        }
+
+        Ok((vm.signal_strength, vm.render()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::input::normalize;
+    use proptest::prelude::*;
+
+    fn command() -> impl Strategy<Value = Command> {
+        prop_oneof![any::<i32>().prop_map(Command::Add), Just(Command::Noop)]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_programs(cmds in prop::collection::vec(command(), 1..50)) {
+            let program: Vec<String> = cmds.iter().map(Command::to_string).collect();
+            prop_assert_eq!(parse(&program.join("\n")), Ok(cmds));
+        }
+    }
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    #[test]
+    fn streams_input() {
+        let input = INPUT.replace('\n', "\r\n");
+
+        assert_eq!(
+            aoc_core::solve_stream::<Day10>(&mut input.as_bytes()).unwrap(),
+            aoc_core::solve::<Day10>(INPUT).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_instructions() {
+        let err = parse("noop\naddx x").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 6, "x"));
+
+        let err = parse("noop\nmulx 2").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 1, "mulx"));
+    }
+
+    #[test]
+    fn draws_crt() {
+        let cmds = parse(&normalize(INPUT)).unwrap();
+
+        insta::assert_snapshot!(execute(&Config::default(), &cmds).render());
+    }
+
+    #[test]
+    fn runs_past_the_screen() {
+        let cmds = parse(&normalize(&INPUT.repeat(2))).unwrap();
+
+        assert_eq!(
+            execute(&Config::default(), &cmds).render(),
+            execute(&Config::default(), &cmds[..cmds.len() / 2]).render()
+        );
+    }
+
+    #[test]
+    fn runs_long_programs_with_large_registers() {
+        let mut cmds = vec![Command::Add(100_000)];
+        cmds.extend([Command::Noop; 30_000]);
+
+        assert_eq!(
+            execute(&Config::default(), &cmds).signal_strength(),
+            100_001 * (20 + 60 + 100 + 140 + 180 + 220)
+        );
+    }
+
+    #[test]
+    fn draws_other_screen_sizes() {
+        let cmds = parse(&normalize(INPUT)).unwrap();
+        let narrow = Config {
+            width: NonZeroUsize::new(20).unwrap(),
+            height: NonZeroUsize::new(12).unwrap(),
+        };
+
+        // The beam wraps every 20 cycles, so each row of the puzzle's screen
+        // starts where a row of the narrow one does.
+        let wide = execute(&Config::default(), &cmds).render();
+        let narrow = execute(&narrow, &cmds).render();
+        let starts: Vec<String> = wide
+            .lines()
+            .map(|row| row.chars().take(20).collect())
+            .collect();
+        assert_eq!(narrow.lines().count(), 12);
+        assert_eq!(narrow.lines().step_by(2).collect::<Vec<_>>(), starts);
+    }
+}
@@ -0,0 +1,641 @@
+//! Day 11: Monkey in the Middle.
+//!
+//! ```
+//! use aoc_11::{parse_op, Expr, Value};
+//!
+//! let op = parse_op("Operation: new = old * 19").unwrap();
+//! assert_eq!(op, Expr::Multiply(Value::Old, Value::Static(19)));
+//! assert_eq!(op.apply(2), 38);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use aoc_core::{ParseError, Solution};
+use aoc_parse::{blocks, comma_list, fail, integer, key_value, literal, parse_all, IResult};
+use nom::branch::alt;
+use nom::character::complete::{char, one_of};
+use nom::combinator::{consumed, map, opt, value, verify};
+use nom::error::context;
+use nom::sequence::{delimited, pair, preceded, tuple};
+use serde::Deserialize;
+
+/// Puzzle constants, read from the `[day11]` section of the workspace
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Rounds simulated in part 1, with relief after every inspection.
+    pub rounds_with_relief: usize,
+    /// Rounds simulated in part 2, without relief.
+    pub rounds_without_relief: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            rounds_with_relief: 20,
+            rounds_without_relief: 10_000,
+        }
+    }
+}
+
+impl aoc_core::config::Section for Config {}
+
+impl Config {
+    /// The installed configuration, or the puzzle's constants if there is
+    /// none.
+    pub fn load() -> Self {
+        aoc_core::config::get(Day11::DAY)
+    }
+}
+
+/// A monkey, the worry levels of the items it holds and how it decides where
+/// to throw them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Monkey {
+    pub initial_items: VecDeque<i64>,
+    pub op: Expr,
+    pub test: Test,
+}
+
+impl fmt::Display for Monkey {
+    /// Writes the monkey's definition as in the puzzle, less the `Monkey N:`
+    /// header, which [`render`] adds.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "  Starting items:")?;
+        let items: Vec<String> = self.initial_items.iter().map(i64::to_string).collect();
+        if !items.is_empty() {
+            write!(f, " {}", items.join(", "))?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "  Operation: new = {}", self.op)?;
+        write!(f, "  {}", self.test)
+    }
+}
+
+/// Picks the monkey an item is thrown to based on its worry level.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Test {
+    pub divisor: i64,
+    pub branch: Branch,
+}
+
+impl Test {
+    /// The monkey an item with worry level `n` is thrown to.
+    pub fn check(&self, n: i64) -> usize {
+        if n % self.divisor == 0 {
+            return self.branch.pass;
+        }
+
+        self.branch.fail
+    }
+}
+
+impl fmt::Display for Test {
+    /// Writes the `Test:` line and the two `If ...` lines after it.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Test: divisible by {}", self.divisor)?;
+        writeln!(f, "    If true: throw to monkey {}", self.branch.pass)?;
+        write!(f, "    If false: throw to monkey {}", self.branch.fail)
+    }
+}
+
+/// Monkeys to throw to when the test passes or fails.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Branch {
+    pub pass: usize,
+    pub fail: usize,
+}
+
+/// How a monkey's inspection changes an item's worry level.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Expr {
+    Multiply(Value, Value),
+    Add(Value, Value),
+}
+
+impl Expr {
+    /// The new worry level given the `old` one. It is wide enough for any
+    /// two operands, so it cannot overflow.
+    pub fn apply(&self, old: i64) -> i128 {
+        let value = |operand: &Value| match operand {
+            Value::Old => i128::from(old),
+            Value::Static(x) => i128::from(*x),
+        };
+
+        match self {
+            Expr::Multiply(a, b) => value(a) * value(b),
+            Expr::Add(a, b) => value(a) + value(b),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Writes the right hand side of the operation, e.g. `old * 19`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Multiply(a, b) => write!(f, "{} * {}", a, b),
+            Expr::Add(a, b) => write!(f, "{} + {}", a, b),
+        }
+    }
+}
+
+/// An operand of an [`Expr`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Value {
+    Old,
+    Static(i64),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Old => write!(f, "old"),
+            Value::Static(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl std::convert::TryFrom<&str> for Value {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        parse_all(input, operand)
+    }
+}
+
+/// Least common multiple of `a` and `b`, or `None` if it overflows.
+fn lcm(a: i64, b: i64) -> Option<i64> {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    (a / x).checked_mul(b)
+}
+
+/// Least common multiple of every monkey's divisor, or `None` if it
+/// overflows.
+fn modulus(monkies: &VecDeque<Monkey>) -> Option<i64> {
+    monkies
+        .iter()
+        .try_fold(1, |acc, monkey| lcm(acc, monkey.test.divisor))
+}
+
+/// Plays out the monkeys' game of keep away.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Simulator {
+    monkies: VecDeque<Monkey>,
+    monkey_inspections: HashMap<usize, usize>,
+    round: usize,
+    /// Whether worry levels are divided by three after each inspection.
+    relief: bool,
+    /// Every test only cares about divisibility, so worry levels can be kept
+    /// modulo the least common multiple of all divisors without changing
+    /// where items land. `None` if it does not fit, which [`parse`] rejects.
+    modulus: Option<i64>,
+}
+
+impl Simulator {
+    /// Starts the game. With `relief`, worry levels are divided by three after
+    /// every inspection.
+    pub fn new(monkies: VecDeque<Monkey>, relief: bool) -> Self {
+        Self {
+            modulus: modulus(&monkies),
+            monkies,
+            round: 0,
+            monkey_inspections: HashMap::new(),
+            relief,
+        }
+    }
+
+    /// Has monkey `i` inspect and throw all of its items.
+    pub fn turn(&mut self, i: usize) {
+        while let Some(item) = self.monkies[i].initial_items.pop_front() {
+            *self.monkey_inspections.entry(i).or_insert(0) += 1;
+
+            // Dividing by three is only exact modulo three times the
+            // modulus, which keeps worry levels with relief bounded too.
+            let worry = self.monkies[i].op.apply(item);
+            let worry = match (self.relief, self.modulus.map(i128::from)) {
+                (true, Some(modulus)) => worry % (3 * modulus) / 3,
+                (true, None) => worry / 3,
+                (false, Some(modulus)) => worry % modulus,
+                (false, None) => worry,
+            };
+            let item = i64::try_from(worry)
+                .expect("parse checked the divisors have a modulus to keep worry levels below");
+            let next = self.monkies[i].test.check(item);
+            tracing::trace!(monkey = i, item, to = next, "throw");
+            self.monkies[next].initial_items.push_back(item);
+        }
+    }
+
+    /// Has every monkey, in turn, inspect and throw all of its items.
+    pub fn round(&mut self) {
+        for i in 0..self.monkies.len() {
+            self.turn(i);
+        }
+
+        self.round += 1;
+        tracing::debug!(
+            round = self.round,
+            inspections = ?(0..self.monkies.len())
+                .map(|i| self.monkey_inspections.get(&i).copied().unwrap_or(0))
+                .collect::<Vec<_>>(),
+            "round"
+        );
+    }
+
+    /// The monkeys, holding their current items.
+    pub fn monkies(&self) -> &VecDeque<Monkey> {
+        &self.monkies
+    }
+
+    /// Number of items each monkey has inspected so far, keyed by monkey.
+    pub fn inspections(&self) -> &HashMap<usize, usize> {
+        &self.monkey_inspections
+    }
+
+    /// Multiplies the inspection counts of the two most active monkeys.
+    pub fn monkey_business(&self) -> usize {
+        let mut inspections: Vec<usize> = self.monkey_inspections.values().copied().collect();
+        inspections.sort();
+
+        inspections.iter().rev().take(2).product()
+    }
+}
+
+/// An operand, `old` or a number.
+fn operand(input: &str) -> IResult<'_, Value> {
+    context(
+        "`old` or a number",
+        alt((
+            value(Value::Old, literal("old")),
+            map(integer, Value::Static),
+        )),
+    )(input)
+}
+
+/// The right hand side of an operation, like `old * 19`. One of the operands
+/// must be `old`.
+fn expr(input: &str) -> IResult<'_, Expr> {
+    let (rest, (first, op, second)) = tuple((
+        operand,
+        delimited(char(' '), context("`+` or `*`", one_of("+*")), char(' ')),
+        operand,
+    ))(input)?;
+
+    if first != Value::Old && second != Value::Old {
+        return fail(input, "`old`");
+    }
+
+    Ok((
+        rest,
+        match op {
+            '+' => Expr::Add(first, second),
+            _ => Expr::Multiply(first, second),
+        },
+    ))
+}
+
+/// Parses an `Operation: new = ...` line.
+pub fn parse_op(def: &str) -> Result<Expr, ParseError> {
+    parse_all(def, operation)
+}
+
+fn operation(input: &str) -> IResult<'_, Expr> {
+    context(
+        "an operation like `Operation: new = old * 19`",
+        key_value("Operation", preceded(literal("new = "), expr)),
+    )(input)
+}
+
+/// The monkey thrown to after a test, as in `If true: throw to monkey 2`,
+/// along with the slice holding its number.
+fn branch<'a>(outcome: &'static str) -> impl FnMut(&'a str) -> IResult<'a, (&'a str, usize)> {
+    key_value(
+        outcome,
+        preceded(
+            literal("throw to monkey "),
+            context("a monkey number", consumed(integer)),
+        ),
+    )
+}
+
+/// Where a monkey's numbers are in the input, to report them.
+struct Slices<'a> {
+    divisor: &'a str,
+    /// The numbers of the monkeys thrown to.
+    targets: [&'a str; 2],
+}
+
+/// The `Test:` line and the two `If ...` lines after it, along with where
+/// their numbers are.
+fn test(input: &str) -> IResult<'_, (Test, Slices<'_>)> {
+    let (input, (divisor_slice, divisor)) = context(
+        "a test like `Test: divisible by 23`",
+        key_value(
+            "Test",
+            preceded(
+                literal("divisible by "),
+                context(
+                    "a non-zero divisor",
+                    consumed(verify(integer, |n: &i64| *n != 0)),
+                ),
+            ),
+        ),
+    )(input)?;
+    let (input, (pass, fail)) = pair(
+        context(
+            "`If true: throw to monkey N`",
+            preceded(char('\n'), branch("If true")),
+        ),
+        context(
+            "`If false: throw to monkey N`",
+            preceded(char('\n'), branch("If false")),
+        ),
+    )(input)?;
+
+    let test = Test {
+        divisor,
+        branch: Branch {
+            pass: pass.1,
+            fail: fail.1,
+        },
+    };
+
+    let slices = Slices {
+        divisor: divisor_slice,
+        targets: [pass.0, fail.0],
+    };
+
+    Ok((input, (test, slices)))
+}
+
+/// A monkey's definition, along with where the numbers of its test are.
+fn monkey(input: &str) -> IResult<'_, (Monkey, Slices<'_>)> {
+    let (input, _) = context(
+        "a `Monkey N:` header",
+        tuple((literal("Monkey "), integer::<usize>, char(':'))),
+    )(input)?;
+    let (input, initial_items) = context(
+        "a list like `Starting items: 79, 98`",
+        preceded(
+            char('\n'),
+            key_value(
+                "Starting items",
+                opt(comma_list(context("a worry level", integer))),
+            ),
+        ),
+    )(input)?;
+    let (input, op) = context("an `Operation:` line", preceded(char('\n'), operation))(input)?;
+    let (input, (test, slices)) = context(
+        "a test like `Test: divisible by 23`",
+        preceded(char('\n'), test),
+    )(input)?;
+
+    let monkey = Monkey {
+        initial_items: initial_items.unwrap_or_default().into(),
+        op,
+        test,
+    };
+
+    Ok((input, (monkey, slices)))
+}
+
+/// Parses every monkey definition.
+pub fn parse(input: &str) -> Result<VecDeque<Monkey>, ParseError> {
+    let (monkies, slices): (VecDeque<_>, Vec<_>) =
+        parse_all(input, blocks(monkey))?.into_iter().unzip();
+
+    // Throwing to a missing monkey, or to itself, would never end the round.
+    for (i, (monkey, slices)) in monkies.iter().zip(&slices).enumerate() {
+        let branch = &monkey.test.branch;
+        for (target, slice) in [branch.pass, branch.fail].into_iter().zip(slices.targets) {
+            if target >= monkies.len() || target == i {
+                return Err(ParseError::at(
+                    input,
+                    slice,
+                    format!("another monkey below {}", monkies.len()),
+                ));
+            }
+        }
+    }
+
+    let mut acc = 1;
+    for (monkey, slices) in monkies.iter().zip(&slices) {
+        acc = lcm(acc, monkey.test.divisor).ok_or_else(|| {
+            ParseError::at(
+                input,
+                slices.divisor,
+                "a divisor keeping the least common multiple of all of them in range",
+            )
+        })?;
+    }
+
+    Ok(monkies)
+}
+
+/// Writes the monkeys back out as [`parse`] reads them, numbering them in
+/// order.
+pub fn render(monkies: &VecDeque<Monkey>) -> String {
+    monkies
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| format!("Monkey {}:\n{}", i, monkey))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Plays the given number of rounds and returns the level of monkey business.
+pub fn simulate(monkies: &VecDeque<Monkey>, rounds: usize, relief: bool) -> usize {
+    let mut sim = Simulator::new(monkies.clone(), relief);
+    for _ in 0..rounds {
+        sim.round();
+    }
+
+    sim.monkey_business()
+}
+
+/// Tracks the monkeys playing keep away with our items.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    /// Each monkey's starting items and behaviour.
+    type Input = VecDeque<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    /// Simulates the rounds with relief after every inspection, 20 in the
+    /// puzzle.
+    fn part1(monkies: &Self::Input) -> Self::Part1 {
+        simulate(monkies, Config::load().rounds_with_relief, true)
+    }
+
+    /// Simulates the rounds without any relief, 10,000 in the puzzle.
+    fn part2(monkies: &Self::Input) -> Self::Part2 {
+        simulate(monkies, Config::load().rounds_without_relief, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn value() -> impl Strategy<Value = Value> {
+        prop_oneof![Just(Value::Old), (0..=i64::MAX).prop_map(Value::Static)]
+    }
+
+    /// Operations with at least one `old` operand, as the parser requires.
+    fn expr() -> impl Strategy<Value = Expr> {
+        (value(), any::<bool>(), any::<bool>()).prop_map(|(other, old_first, add)| {
+            let (a, b) = if old_first {
+                (Value::Old, other)
+            } else {
+                (other, Value::Old)
+            };
+
+            if add {
+                Expr::Add(a, b)
+            } else {
+                Expr::Multiply(a, b)
+            }
+        })
+    }
+
+    /// Monkeys that each throw to other monkeys of the `n` in the game.
+    fn monkies() -> impl Strategy<Value = VecDeque<Monkey>> {
+        (2..8usize).prop_flat_map(|n| {
+            let monkey = (
+                prop::collection::vec_deque(0..1_000_000i64, 0..10),
+                expr(),
+                // Small enough for the least common multiple of up to seven
+                // of them to fit, as parse requires.
+                1..=500i64,
+                1..n,
+                1..n,
+            );
+
+            prop::collection::vec(monkey, n).prop_map(move |monkies| {
+                monkies
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (initial_items, op, divisor, pass, fail))| Monkey {
+                        initial_items,
+                        op,
+                        test: Test {
+                            divisor,
+                            // Offsets from 1 to n - 1 never land on the monkey
+                            // itself.
+                            branch: Branch {
+                                pass: (i + pass) % n,
+                                fail: (i + fail) % n,
+                            },
+                        },
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_operations(op in expr()) {
+            let line = format!("Operation: new = {}", op);
+            prop_assert_eq!(parse_op(&line), Ok(op));
+        }
+
+        #[test]
+        fn round_trips_monkeys(monkies in monkies()) {
+            prop_assert_eq!(parse(&render(&monkies)), Ok(monkies));
+        }
+    }
+
+    #[test]
+    fn applies_operations() {
+        for (op, worry) in [
+            ("old * 19", 38),
+            ("old * old", 4),
+            ("6 + old", 8),
+            ("old + old", 4),
+        ] {
+            let line = format!("Operation: new = {}", op);
+            assert_eq!(parse_op(&line).unwrap().apply(2), worry);
+        }
+
+        let err = parse_op("Operation: new = 3 * 5").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (18, "`old`"));
+        assert_eq!(
+            Expr::Multiply(Value::Static(3), Value::Static(5)).apply(2),
+            15
+        );
+    }
+
+    #[test]
+    fn keeps_large_worry_levels_in_range() {
+        let monkey = |n: usize, items: &str, divisor: i64| {
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = old * old\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                n, items, divisor, 1 - n, 1 - n
+            )
+        };
+        let input = [
+            monkey(0, "3000000000", 1_000_000_007),
+            monkey(1, "", 998_244_353),
+        ]
+        .join("\n\n");
+        let monkies = parse(&input).unwrap();
+
+        for relief in [true, false] {
+            let mut simulator = Simulator::new(monkies.clone(), relief);
+            for _ in 0..20 {
+                simulator.round();
+            }
+            assert_eq!(simulator.monkey_business(), 400);
+        }
+    }
+
+    #[test]
+    fn rejects_divisors_out_of_range() {
+        let monkey = |n: usize, divisor: i64| {
+            format!(
+                "Monkey {}:\n  Starting items: 79\n  Operation: new = old + 1\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                n, divisor, 1 - n, 1 - n
+            )
+        };
+        let input = [monkey(0, i64::MAX), monkey(1, i64::MAX - 1)].join("\n\n");
+
+        let err = parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (11, 22));
+        assert_eq!(lcm(6, 4), Some(12));
+        assert_eq!(lcm(i64::MAX, 2), None);
+    }
+
+    #[test]
+    fn rejects_malformed_monkeys() {
+        let monkey = "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 1\n    If false: throw to monkey 1";
+
+        let err = parse(&monkey.replace("* 19", "/ 19")).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 24, "/"));
+
+        let err = parse(&monkey.replace("79", "79, x")).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 23, "x"));
+
+        let err = parse(monkey).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (5, 30, "1"));
+
+        let err = parse(&monkey[..monkey.len() - 30]).unwrap_err();
+        assert_eq!(err.found, "");
+    }
+}